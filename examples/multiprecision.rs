@@ -0,0 +1,121 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A small multi-precision unsigned integer built on `UnsignedBitwise` words.
+//!
+//! Run it with `cargo run --example multiprecision`.
+
+use std::fmt;
+use xbitwise::UnsignedBitwise;
+
+/// Unsigned integer stored as little-endian words
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct MultiPrecision<T> {
+    words: Vec<T>,
+}
+
+impl<T: UnsignedBitwise> MultiPrecision<T> {
+    fn from_words(words: &[T]) -> Self {
+        let mut number = MultiPrecision { words: words.to_vec() };
+        number.normalize();
+        number
+    }
+
+    fn normalize(&mut self) {
+        while self.words.last() == Some(&T::zero()) {
+            self.words.pop();
+        }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        MultiPrecision::from_words(&add_words(&self.words, &other.words))
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        MultiPrecision::from_words(&mul_words(&self.words, &other.words))
+    }
+}
+
+/// Adds two little-endian slices of words, propagating the carry.
+fn add_words<T: UnsignedBitwise>(lhs: &[T], rhs: &[T]) -> Vec<T> {
+    let len = lhs.len().max(rhs.len());
+    let mut result = Vec::with_capacity(len + 1);
+    let mut carry = false;
+    for index in 0..len {
+        let a = lhs.get(index).copied().unwrap_or_else(T::zero);
+        let b = rhs.get(index).copied().unwrap_or_else(T::zero);
+        let (sum, carry_out) = UnsignedBitwise::carrying_add(a, b, carry);
+        result.push(sum);
+        carry = carry_out;
+    }
+    if carry {
+        result.push(T::one());
+    }
+    result
+}
+
+/// Multiplies two little-endian slices of words with the schoolbook algorithm.
+fn mul_words<T: UnsignedBitwise>(lhs: &[T], rhs: &[T]) -> Vec<T> {
+    let mut result = vec![T::zero(); lhs.len() + rhs.len()];
+    for (i, &a) in lhs.iter().enumerate() {
+        let mut carry = T::zero();
+        for (j, &b) in rhs.iter().enumerate() {
+            let (lo, hi) = UnsignedBitwise::widening_mul(a, b);
+            let (lo, first) = UnsignedBitwise::carrying_add(lo, result[i + j], false);
+            let (lo, second) = UnsignedBitwise::carrying_add(lo, carry, false);
+            result[i + j] = lo;
+            // `a * b + result + carry` always fits in two words, so this never overflows.
+            let (hi, _) = UnsignedBitwise::carrying_add(hi, T::zero(), first);
+            let (hi, _) = UnsignedBitwise::carrying_add(hi, T::zero(), second);
+            carry = hi;
+        }
+        result[i + rhs.len()] = carry;
+    }
+    result
+}
+
+impl<T: UnsignedBitwise> fmt::Display for MultiPrecision<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut digits = String::new();
+        for &word in self.words.iter().rev() {
+            for offset in (0..T::bit_size()).step_by(4).rev() {
+                let nibble = word.extract_field_unchecked(offset, 4);
+                let value: u32 = nibble.iter_ones().map(|bit| 1 << bit).sum();
+                digits.push(std::char::from_digit(value, 16).unwrap());
+            }
+        }
+        let digits = digits.trim_start_matches('0');
+        write!(f, "0x{}", if digits.is_empty() { "0" } else { digits })
+    }
+}
+
+fn to_bytes(value: u128) -> Vec<u8> {
+    (0..16).map(|index| (value >> (index * 8)) as u8).collect()
+}
+
+fn main() {
+    // Byte-sized words against the native 128-bit arithmetic.
+    let samples = [0u128, 1, 0xFF, 0x1_0000, 0xDEAD_BEEF, 0x0123_4567_89AB_CDEF, 0xFFFF_FFFF_FFFF_FFFF];
+    for &a in &samples {
+        for &b in &samples {
+            let lhs = MultiPrecision::from_words(&to_bytes(a));
+            let rhs = MultiPrecision::from_words(&to_bytes(b));
+            assert_eq!(lhs.add(&rhs), MultiPrecision::from_words(&to_bytes(a + b)));
+            assert_eq!(lhs.mul(&rhs), MultiPrecision::from_words(&to_bytes(a * b)));
+        }
+    }
+
+    // 30! needs more than 64 bits, so it spans several `u64` words.
+    let mut factorial = MultiPrecision::from_words(&[1u64]);
+    for n in 1..=30u64 {
+        factorial = factorial.mul(&MultiPrecision::from_words(&[n]));
+    }
+    assert_eq!(factorial, MultiPrecision::from_words(&[0x865D_F5DD_5400_0000, 0x0D13_F637_0F96]));
+    println!("30! = {}", factorial);
+
+    let sum = factorial.add(&factorial);
+    println!("2 * 30! = {}", sum);
+}
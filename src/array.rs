@@ -0,0 +1,736 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fixed-width bitsets wider than the primitive integers.
+//!
+//! A [`BitArray<N>`](BitArray) is stored in `N / 64` `u64` words, as chosen by the [`Storage`]
+//! implementation of [`Bits<N>`](Bits).
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Not, Shl, Shr, Sub};
+
+use crate::bitorder::{self, BitOrder, ByteOrder};
+use crate::{range_to_bounds, BitSlice, BitSliceMut, Bitwise};
+
+const WORD_BITS: usize = 64;
+
+/// A width in bits, only used to select the storage of a [`BitArray`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bits<const N: usize>;
+
+/// The storage of the [`BitArray`] of a width
+///
+/// It is implemented for every `Bits<N>` with `N` a multiple of 64 between 64 and 1024, so
+/// other widths, zero included, do not compile:
+///
+/// ```compile_fail
+/// use xbitwise::{BitArray, Bitwise};
+///
+/// BitArray::<0>::zero();
+/// ```
+pub trait Storage {
+    /// The `N / 64` words that hold `N` bits
+    type Words: Copy + Default + Eq + Hash + fmt::Debug + AsRef<[u64]> + AsMut<[u64]>;
+}
+
+macro_rules! impl_storage {
+    ($($bits:literal => $words:literal)*) => {$(
+        impl Storage for Bits<$bits> {
+            type Words = [u64; $words];
+        }
+
+        impl From<[u64; $words]> for BitArray<$bits> {
+            fn from(words: [u64; $words]) -> Self {
+                BitArray::from_words(words)
+            }
+        }
+    )*};
+}
+
+impl_storage!(64 => 1 128 => 2 192 => 3 256 => 4 320 => 5 384 => 6 448 => 7 512 => 8 576 => 9 640 => 10
+    704 => 11 768 => 12 832 => 13 896 => 14 960 => 15 1024 => 16);
+
+type Words<const N: usize> = <Bits<N> as Storage>::Words;
+
+/// A bitset of `N` bits stored as `N / 64` little-endian `u64` words
+///
+/// **Note:** `N` is a multiple of 64 between 64 and 1024. Behaves like an unsigned integer of
+/// `N` bits: the arithmetic operators wrap around, the shifts are logical and the order is the
+/// numeric one. Shifting by `N` or more bits and dividing by zero panic.
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::{BitArray256, Bitwise};
+///
+/// let mask = BitArray256::zero().set_range_unchecked(100..200);
+/// assert_eq!(mask.count_ones_in_range_unchecked(..), 100);
+/// assert_eq!(mask.lowest_set_bit(), Some(100));
+/// assert_eq!(BitArray256::bit_size(), 256);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitArray<const N: usize>
+where
+    Bits<N>: Storage,
+{
+    words: Words<N>,
+}
+
+/// A bitset of 256 bits
+pub type BitArray256 = BitArray<256>;
+
+/// A bitset of 512 bits
+pub type BitArray512 = BitArray<512>;
+
+impl<const N: usize> BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    const WORDS: usize = N / WORD_BITS;
+
+    /// Creates a bitset from its words, the least significant word first
+    pub fn from_words(words: Words<N>) -> Self {
+        BitArray { words }
+    }
+
+    /// Returns the words of the bitset, the least significant word first
+    pub fn as_words(&self) -> &Words<N> {
+        &self.words
+    }
+
+    /// Consumes the bitset and returns its words, the least significant word first
+    pub fn into_words(self) -> Words<N> {
+        self.words
+    }
+
+    /// Returns a view of the bits of the bitset, numbered in the given order across the
+    /// whole bitset
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::bitorder::Msb0;
+    /// use xbitwise::{BitArray256, Bitwise};
+    ///
+    /// let mask = BitArray256::zero().set_bit_unchecked(255);
+    /// assert_eq!(mask.as_bit_slice(Msb0).get_bit(0), Some(true));
+    /// assert_eq!(mask.as_bit_slice(Msb0).iter_ones().collect::<Vec<_>>(), [0]);
+    /// ```
+    pub fn as_bit_slice<O: BitOrder>(&self, order: O) -> BitSlice<'_, Self, O> {
+        BitSlice::with_order(std::slice::from_ref(self), order)
+    }
+
+    /// Returns a mutable view of the bits of the bitset, numbered in the given order across
+    /// the whole bitset
+    pub fn as_bit_slice_mut<O: BitOrder>(&mut self, order: O) -> BitSliceMut<'_, Self, O> {
+        BitSliceMut::with_order(std::slice::from_mut(self), order)
+    }
+
+    /// Returns the `N / 8` bytes of the bitset in the byte order `E`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::bitorder::{BigEndian, LittleEndian};
+    /// use xbitwise::BitArray;
+    ///
+    /// let bits = BitArray::<128>::from_words([0x0102, 0x0300_0000_0000_0000]);
+    /// let bytes = bits.to_bytes::<BigEndian>();
+    /// assert_eq!((bytes[0], bytes[14], bytes[15]), (0x03, 0x01, 0x02));
+    /// assert_eq!(bits.to_bytes::<LittleEndian>()[..2], [0x02, 0x01]);
+    /// ```
+    pub fn to_bytes<E: ByteOrder>(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(N / 8);
+        bitorder::write_word::<Self, E>(*self, &mut bytes);
+        bytes
+    }
+
+    /// Reads a bitset from `N / 8` bytes in the byte order `E`
+    ///
+    /// **Note:** Returns `None` when `bytes` does not hold exactly `N / 8` bytes.
+    pub fn from_bytes<E: ByteOrder>(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != N / 8 {
+            return None;
+        }
+        Some(bitorder::read_word::<Self, E>(bytes))
+    }
+
+    fn map_words<F: Fn(u64, u64) -> u64>(self, other: Self, f: F) -> Self {
+        let mut words = self.words;
+        for (word, &other) in words.as_mut().iter_mut().zip(other.words.as_ref()) {
+            *word = f(*word, other);
+        }
+        BitArray { words }
+    }
+
+    fn count_ones(self) -> usize {
+        self.words.as_ref().iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    fn trailing_zeros(self) -> usize {
+        let words = self.words.as_ref();
+        match words.iter().position(|&word| word != 0) {
+            Some(index) => index * WORD_BITS + words[index].trailing_zeros() as usize,
+            None => Self::bit_size(),
+        }
+    }
+
+    fn leading_zeros(self) -> usize {
+        let words = self.words.as_ref();
+        match words.iter().rposition(|&word| word != 0) {
+            Some(index) => (Self::WORDS - 1 - index) * WORD_BITS + words[index].leading_zeros() as usize,
+            None => Self::bit_size(),
+        }
+    }
+
+    fn reverse_bits(self) -> Self {
+        let mut words = Words::<N>::default();
+        for (word, &source) in words.as_mut().iter_mut().zip(self.words.as_ref().iter().rev()) {
+            *word = source.reverse_bits();
+        }
+        BitArray { words }
+    }
+
+    fn shift_left(self, shift: usize) -> Self {
+        let (skip, offset) = (shift / WORD_BITS, shift % WORD_BITS);
+        let source = self.words.as_ref();
+        let mut words = Words::<N>::default();
+        for (index, word) in words.as_mut().iter_mut().enumerate().skip(skip) {
+            *word = source[index - skip] << offset;
+            if offset != 0 && index > skip {
+                *word |= source[index - skip - 1] >> (WORD_BITS - offset);
+            }
+        }
+        BitArray { words }
+    }
+
+    fn shift_right(self, shift: usize) -> Self {
+        let (skip, offset) = (shift / WORD_BITS, shift % WORD_BITS);
+        let source = self.words.as_ref();
+        let mut words = Words::<N>::default();
+        for (index, word) in words.as_mut().iter_mut().take(Self::WORDS - skip).enumerate() {
+            *word = source[index + skip] >> offset;
+            if offset != 0 && index + skip + 1 < Self::WORDS {
+                *word |= source[index + skip + 1] << (WORD_BITS - offset);
+            }
+        }
+        BitArray { words }
+    }
+}
+
+/// Builds the bitset that holds the given bit positions
+///
+/// # Panics
+///
+/// Panics when a position is not less than `bit_size()`; see
+/// [`Bitwise::from_indices`] for the checked version.
+impl<const N: usize> FromIterator<usize> for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    fn from_iter<I: IntoIterator<Item = usize>>(indices: I) -> Self {
+        indices.into_iter().fold(Self::zero(), |set, index| {
+            set.insert(index).expect("bit index out of range for the bitset")
+        })
+    }
+}
+
+impl<const N: usize> PartialOrd for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.words.as_ref().iter().rev().cmp(other.words.as_ref().iter().rev())
+    }
+}
+
+impl<const N: usize> Not for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.map_words(self, |word, _| !word)
+    }
+}
+
+impl<const N: usize> BitAnd for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.map_words(rhs, |word, other| word & other)
+    }
+}
+
+impl<const N: usize> BitOr for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.map_words(rhs, |word, other| word | other)
+    }
+}
+
+impl<const N: usize> BitXor for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        self.map_words(rhs, |word, other| word ^ other)
+    }
+}
+
+impl<const N: usize> Shl<u32> for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn shl(self, rhs: u32) -> Self {
+        assert!((rhs as usize) < Self::bit_size(), "attempt to shift left with overflow");
+        self.shift_left(rhs as usize)
+    }
+}
+
+impl<const N: usize> Shr<u32> for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn shr(self, rhs: u32) -> Self {
+        assert!((rhs as usize) < Self::bit_size(), "attempt to shift right with overflow");
+        self.shift_right(rhs as usize)
+    }
+}
+
+impl<const N: usize> Add for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (left, right) = (self.words.as_ref(), rhs.words.as_ref());
+        let mut words = Words::<N>::default();
+        let mut carry = false;
+        for (index, word) in words.as_mut().iter_mut().enumerate() {
+            let (sum, first) = left[index].overflowing_add(right[index]);
+            let (sum, second) = sum.overflowing_add(carry as u64);
+            *word = sum;
+            carry = first || second;
+        }
+        BitArray { words }
+    }
+}
+
+impl<const N: usize> Sub for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let (left, right) = (self.words.as_ref(), rhs.words.as_ref());
+        let mut words = Words::<N>::default();
+        let mut borrow = false;
+        for (index, word) in words.as_mut().iter_mut().enumerate() {
+            let (difference, first) = left[index].overflowing_sub(right[index]);
+            let (difference, second) = difference.overflowing_sub(borrow as u64);
+            *word = difference;
+            borrow = first || second;
+        }
+        BitArray { words }
+    }
+}
+
+impl<const N: usize> Mul for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (left, right) = (self.words.as_ref(), rhs.words.as_ref());
+        let mut words = Words::<N>::default();
+        let product_words = words.as_mut();
+        for i in 0..Self::WORDS {
+            let mut carry = 0u128;
+            // The products that land beyond the last word are dropped, wrapping the result.
+            for j in 0..Self::WORDS - i {
+                let product = left[i] as u128 * right[j] as u128 + product_words[i + j] as u128 + carry;
+                product_words[i + j] = product as u64;
+                carry = product >> WORD_BITS;
+            }
+        }
+        BitArray { words }
+    }
+}
+
+impl<const N: usize> Div for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        assert!(rhs != Self::zero(), "attempt to divide by zero");
+        let mut quotient = Self::zero();
+        let mut remainder = Self::zero();
+        for index in (0..self.highest_set_bit().map_or(0, |highest| highest + 1)).rev() {
+            // A remainder with its top bit on is larger than the divisor once shifted.
+            let overflow = remainder.get_bit_unchecked(Self::bit_size() - 1);
+            remainder = remainder.shift_left(1).update_bit_unchecked(0, self.get_bit_unchecked(index));
+            if overflow || remainder >= rhs {
+                remainder = remainder - rhs;
+                quotient = quotient.set_bit_unchecked(index);
+            }
+        }
+        quotient
+    }
+}
+
+impl<const N: usize> Bitwise for BitArray<N>
+where
+    Bits<N>: Storage,
+{
+    fn get_bit_unchecked(self, index: usize) -> bool {
+        (self.words.as_ref()[index / WORD_BITS] >> (index % WORD_BITS)) & 1 == 1
+    }
+
+    fn set_bit_unchecked(mut self, index: usize) -> Self {
+        self.words.as_mut()[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+        self
+    }
+
+    fn get_range_mask_unchecked<R: RangeBounds<usize>>(range: R) -> Self {
+        let (start, end) = range_to_bounds(&range, Self::bit_size());
+        let end = end.min(Self::bit_size());
+        let mut words = Words::<N>::default();
+        for (index, word) in words.as_mut().iter_mut().enumerate() {
+            let low = (index * WORD_BITS).max(start);
+            let high = ((index + 1) * WORD_BITS).min(end);
+            if low < high {
+                let width = high - low;
+                let ones = if width == WORD_BITS { !0 } else { (1 << width) - 1 };
+                *word = ones << (low % WORD_BITS);
+            }
+        }
+        BitArray { words }
+    }
+
+    fn count_ones_in_range_unchecked<R: RangeBounds<usize>>(self, range: R) -> usize {
+        (self & Self::get_range_mask_unchecked(range)).count_ones()
+    }
+
+    fn extract_field_unchecked(self, offset: usize, width: usize) -> Self {
+        if width == 0 {
+            return Self::zero();
+        }
+        self.shift_right(offset) & Self::get_range_mask_unchecked(..width)
+    }
+
+    fn clear_bit_unchecked(mut self, index: usize) -> Self {
+        self.words.as_mut()[index / WORD_BITS] &= !(1 << (index % WORD_BITS));
+        self
+    }
+
+    fn flip_bit_unchecked(mut self, index: usize) -> Self {
+        self.words.as_mut()[index / WORD_BITS] ^= 1 << (index % WORD_BITS);
+        self
+    }
+
+    fn count_trailing_zeros(self) -> usize {
+        self.trailing_zeros()
+    }
+
+    fn count_leading_zeros(self) -> usize {
+        self.leading_zeros()
+    }
+
+    fn select(self, n: usize) -> Option<usize> {
+        let mut remaining = n;
+        for (index, &word) in self.words.as_ref().iter().enumerate() {
+            let ones = word.count_ones() as usize;
+            if remaining < ones {
+                let word = (0..remaining).fold(word, |word, _| word & (word - 1));
+                return Some(index * WORD_BITS + word.trailing_zeros() as usize);
+            }
+            remaining -= ones;
+        }
+        None
+    }
+
+    fn reverse_low_bits_unchecked(self, n: usize) -> Self {
+        if n == 0 {
+            return Self::zero();
+        }
+        self.reverse_bits().extract_field_unchecked(Self::bit_size() - n, n)
+    }
+
+    fn zero() -> Self {
+        BitArray { words: Words::<N>::default() }
+    }
+
+    fn one() -> Self {
+        Self::zero().set_bit_unchecked(0)
+    }
+
+    fn bit_size() -> usize {
+        N
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{BitArray, BitArray256, BitArray512};
+    use crate::bitorder::{BigEndian, LittleEndian, Lsb0, Msb0};
+    use crate::order::SetOrder;
+    use crate::Bitwise;
+
+    type Wide = BitArray<128>;
+
+    fn wide(value: u128) -> Wide {
+        BitArray::from_words([value as u64, (value >> 64) as u64])
+    }
+
+    const SAMPLES: [u128; 9] = [
+        0,
+        1,
+        u128::MAX,
+        1 << 63,
+        1 << 64,
+        1 << 127,
+        0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210,
+        0xFFFF_FFFF_FFFF_FFFF,
+        0x8000_0000_0000_0001_0000_0000_0000_F00D,
+    ];
+
+    #[test]
+    fn operators_match_u128() {
+        for &a in &SAMPLES {
+            assert_eq!(!wide(a), wide(!a));
+            for shift in &[0u32, 1, 5, 63, 64, 65, 100, 127] {
+                assert_eq!(wide(a) << *shift, wide(a << shift));
+                assert_eq!(wide(a) >> *shift, wide(a >> shift));
+            }
+            for &b in &SAMPLES {
+                assert_eq!(wide(a) & wide(b), wide(a & b));
+                assert_eq!(wide(a) | wide(b), wide(a | b));
+                assert_eq!(wide(a) ^ wide(b), wide(a ^ b));
+                assert_eq!(wide(a) + wide(b), wide(a.wrapping_add(b)));
+                assert_eq!(wide(a) - wide(b), wide(a.wrapping_sub(b)));
+                assert_eq!(wide(a) * wide(b), wide(a.wrapping_mul(b)));
+                if b != 0 {
+                    assert_eq!(wide(a) / wide(b), wide(a / b));
+                }
+                assert_eq!(wide(a).cmp(&wide(b)), a.cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to shift left with overflow")]
+    fn shift_overflow() {
+        let _ = BitArray256::one() << 256;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn divide_by_zero() {
+        let _ = BitArray256::one() / BitArray256::zero();
+    }
+
+    #[test]
+    fn methods_match_u128() {
+        for &a in &SAMPLES {
+            let x = wide(a);
+            assert_eq!(x.parity(), a.parity());
+            assert_eq!(x.count_trailing_zeros(), a.count_trailing_zeros());
+            assert_eq!(x.count_leading_zeros(), a.count_leading_zeros());
+            assert_eq!(x.lowest_set_bit(), a.lowest_set_bit());
+            assert_eq!(x.highest_set_bit(), a.highest_set_bit());
+            assert_eq!(x.lowest_clear_bit(), a.lowest_clear_bit());
+            assert_eq!(x.highest_clear_bit(), a.highest_clear_bit());
+            assert_eq!(x.next_same_popcount(), a.next_same_popcount().map(wide));
+            assert_eq!(x.to_gray(), wide(a.to_gray()));
+            assert_eq!(x.from_gray(), wide(a.from_gray()));
+            assert_eq!(x.floor_log2(), a.floor_log2());
+            assert_eq!(x.ceil_log2(), a.ceil_log2());
+            assert_eq!(x.is_power_of_two(), Bitwise::is_power_of_two(a));
+            assert_eq!(x.next_power_of_two_checked(), a.next_power_of_two_checked().map(wide));
+            assert_eq!(x.prev_power_of_two(), a.prev_power_of_two().map(wide));
+            assert_eq!(x.isolate_lowest_one(), wide(Bitwise::isolate_lowest_one(a)));
+            assert_eq!(x.clear_lowest_one(), wide(a.clear_lowest_one()));
+            assert_eq!(x.isolate_highest_one(), wide(Bitwise::isolate_highest_one(a)));
+            assert_eq!(x.lowest_zero_mask(), wide(a.lowest_zero_mask()));
+            assert_eq!(x.cardinality(), a.cardinality());
+            assert!(x.iter_ones().eq(a.iter_ones()));
+            assert!(x.iter_zeros().rev().eq(a.iter_zeros().rev()));
+            for n in 0..=128 {
+                assert_eq!(x.select(n), a.select(n));
+                assert_eq!(x.reverse_low_bits(n), a.reverse_low_bits(n).map(wide));
+                assert_eq!(x.sign_extend_from(n), a.sign_extend_from(n).map(wide));
+                assert_eq!(x.next_set_bit(n), a.next_set_bit(n));
+                assert_eq!(x.prev_clear_bit(n), a.prev_clear_bit(n));
+            }
+            for &(start, end) in &[(0, 0), (0, 128), (3, 70), (64, 128), (60, 68), (127, 128)] {
+                assert_eq!(x.set_range(start..end), a.set_range(start..end).map(wide));
+                assert_eq!(x.clear_range(start..end), a.clear_range(start..end).map(wide));
+                assert_eq!(x.flip_range(start..end), a.flip_range(start..end).map(wide));
+                assert_eq!(x.count_ones_in_range(start..end), a.count_ones_in_range(start..end));
+                assert_eq!(x.extract_field(start, end - start), a.extract_field(start, end - start).map(wide));
+                assert_eq!(
+                    x.insert_field(start, end - start, wide(!a)),
+                    a.insert_field(start, end - start, !a).map(wide)
+                );
+                assert_eq!(x.reverse_bits_in_range(start..end), a.reverse_bits_in_range(start..end).map(wide));
+                assert_eq!(x.rotate_range_left(start..end, 7), a.rotate_range_left(start..end, 7).map(wide));
+                assert_eq!(x.rotate_range_right(start..end, 70), a.rotate_range_right(start..end, 70).map(wide));
+            }
+            for &b in &SAMPLES {
+                let y = wide(b);
+                assert_eq!(x.hamming_distance(y), a.hamming_distance(b));
+                assert_eq!(Bitwise::deposit_bits(x, y), wide(Bitwise::deposit_bits(a, b)));
+                assert_eq!(Bitwise::extract_bits(x, y), wide(Bitwise::extract_bits(a, b)));
+                for &n in &[0, 1, 64, 100, 128] {
+                    assert_eq!(Wide::funnel_shl(x, y, n), wide(<u128 as Bitwise>::funnel_shl(a, b, n)));
+                    assert_eq!(Wide::funnel_shr(x, y, n), wide(<u128 as Bitwise>::funnel_shr(a, b, n)));
+                }
+                assert_eq!(x.is_subset_of(y), a.is_subset_of(b));
+                assert_eq!(x.is_disjoint(y), a.is_disjoint(b));
+                assert_eq!(x.difference(y), wide(a.difference(b)));
+                for &order in &[SetOrder::Colex, SetOrder::Lex, SetOrder::Popcount] {
+                    assert_eq!(x.cmp_in_order(y, order), a.cmp_in_order(b, order));
+                }
+            }
+            for &order in &[SetOrder::Colex, SetOrder::Lex, SetOrder::Popcount] {
+                assert_eq!(x.next_in_order(order), a.next_in_order(order).map(wide));
+                assert_eq!(x.prev_in_order(order), a.prev_in_order(order).map(wide));
+            }
+        }
+    }
+
+    #[test]
+    fn get_range_mask() {
+        let mask = BitArray256::get_range_mask_unchecked(60..130);
+        assert_eq!(mask.as_words(), &[0xF << 60, !0, 0b11, 0]);
+        assert_eq!(BitArray256::get_range_mask(..), Some(BitArray256::from_words([!0; 4])));
+        assert_eq!(BitArray256::get_range_mask(..=256), None);
+    }
+
+    #[test]
+    fn bit_size() {
+        assert_eq!(BitArray256::bit_size(), 256);
+        assert_eq!(BitArray512::bit_size(), 512);
+        assert_eq!(BitArray512::zero().set_bit(511).and_then(|mask| mask.highest_set_bit()), Some(511));
+        assert_eq!(BitArray512::zero().set_bit(512), None);
+        assert_eq!(BitArray::<64>::bit_size(), 64);
+        assert_eq!(BitArray::<192>::from([0, 0, 1 << 63]).highest_set_bit(), Some(191));
+        assert_eq!(BitArray::<1024>::zero().set_all().count_ones_in_range_unchecked(..), 1024);
+        assert_eq!(std::mem::size_of::<BitArray<320>>(), 40);
+    }
+
+    #[test]
+    fn conversions() {
+        let mask: BitArray256 = (0..256).step_by(64).collect();
+        assert_eq!(mask.into_words(), [1; 4]);
+        assert_eq!(BitArray256::from_indices(vec![0, 256]), None);
+        assert_eq!(0xF0u8.sign_extend_to::<BitArray256>(), BitArray256::from_words([!0xF, !0, !0, !0]));
+        assert_eq!(BitArray256::from([!0; 4]).truncate_to::<u16>(), u16::MAX);
+        assert_eq!(BitArray256::default(), BitArray256::zero());
+    }
+
+    #[test]
+    fn bit_and_byte_orders() {
+        let value = wide(0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210);
+        let big_endian = value.to_bytes::<BigEndian>();
+        assert_eq!(big_endian, 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128.to_be_bytes());
+        assert_eq!(value.to_bytes::<LittleEndian>(), 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210u128.to_le_bytes());
+        assert_eq!(Wide::from_bytes::<BigEndian>(&big_endian), Some(value));
+        assert_eq!(Wide::from_bytes::<LittleEndian>(&big_endian[..15]), None);
+
+        let msb0: Vec<usize> = value.as_bit_slice(Msb0).iter_ones().collect();
+        let expected: Vec<usize> = value.iter_ones().map(|index| 127 - index).rev().collect();
+        assert_eq!(msb0, expected);
+        assert_eq!(value.as_bit_slice(Lsb0).iter_ones().collect::<Vec<_>>(), value.iter_ones().collect::<Vec<_>>());
+
+        let mut bits = BitArray256::zero();
+        bits.as_bit_slice_mut(Msb0).set_range(0..4).unwrap();
+        assert_eq!(bits, BitArray256::get_range_mask_unchecked(252..));
+    }
+
+    #[test]
+    fn arithmetic_across_words() {
+        let max = BitArray256::zero().set_all();
+        assert_eq!(max + BitArray256::one(), BitArray256::zero());
+        assert_eq!(BitArray256::zero() - BitArray256::one(), max);
+        let big = BitArray256::one() << 200;
+        assert_eq!((big * big).count_ones_in_range_unchecked(..), 0);
+        assert_eq!(max / big, BitArray256::get_range_mask_unchecked(..56));
+        assert_eq!((max / BitArray256::from_words([3, 0, 0, 0])) * BitArray256::from_words([3, 0, 0, 0]), max);
+        assert_eq!(big.cmp(&max), Ordering::Less);
+    }
+
+    #[test]
+    fn generic_code_beyond_128_bits() {
+        let x = BitArray256::one() << 100;
+        let y = BitArray256::one() << 80;
+        let code = crate::hilbert::hilbert_index_2d(128, x, y).unwrap();
+        assert_eq!(crate::hilbert::hilbert_coordinates_2d(128, code), Some((x, y)));
+        assert_eq!(BitArray256::combinations(256, 1).count(), 256);
+        assert_eq!(BitArray256::get_range_mask_unchecked(..255).rank_combination(), Some(0));
+    }
+
+    #[test]
+    fn rank_combinations_beyond_128_bits() {
+        let low = BitArray256::get_range_mask_unchecked(..128);
+        assert_eq!(BitArray256::unrank_combination(0, 128), Some(low));
+        assert_eq!(low.rank_combination(), Some(0));
+
+        let high = BitArray256::from_indices(vec![200, 250, 251, 252]).unwrap();
+        assert_eq!(high.rank_combination(), Some(166_695_325));
+        assert_eq!(BitArray256::unrank_combination(166_695_325, 4), Some(high));
+
+        let mask = BitArray256::get_range_mask_unchecked(..63).set_bit_unchecked(70);
+        assert_eq!(mask.rank_combination(), Some(131_115_985));
+        assert_eq!(BitArray256::unrank_combination(131_115_985, 64), Some(mask));
+        for (rank, mask) in BitArray256::combinations(256, 64).take(100).enumerate() {
+            assert_eq!(mask.rank_combination(), Some(rank as u128));
+            assert_eq!(BitArray256::unrank_combination(rank as u128, 64), Some(mask));
+        }
+
+        let last = BitArray512::unrank_combination(u128::MAX, 100).unwrap();
+        assert_eq!(last.count_ones_in_range_unchecked(..), 100);
+        assert_eq!(last.rank_combination(), Some(u128::MAX));
+        let top = BitArray512::get_range_mask_unchecked(412..);
+        assert_eq!(top.rank_combination(), None);
+        assert_eq!(BitArray512::unrank_combination(0, 512), Some(BitArray512::zero().set_all()));
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bit and byte orders of the multi-word containers.
+//!
+//! A [`BitOrder`] decides which bit of each word holds the first position of a `BitVec`
+//! or a `BitSlice`: [`Lsb0`] starts at the least significant bit, as `get_bit` does, and
+//! [`Msb0`] starts at the most significant bit, as datasheets and network RFCs do. A
+//! [`ByteOrder`] decides how each word is written out when the bits are serialised.
+
+use crate::Bitwise;
+
+/// The bit of each word that holds the first position of a container
+pub trait BitOrder {
+    /// Moves the bits of a stored word so that the first position is the least significant
+    /// bit. Applying it twice gives back the stored word.
+    fn arrange<T: Bitwise>(word: T) -> T;
+}
+
+/// The first position is the least significant bit of each word
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Lsb0;
+
+/// The first position is the most significant bit of each word
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Msb0;
+
+impl BitOrder for Lsb0 {
+    fn arrange<T: Bitwise>(word: T) -> T {
+        word
+    }
+}
+
+impl BitOrder for Msb0 {
+    fn arrange<T: Bitwise>(word: T) -> T {
+        word.reverse_low_bits_unchecked(T::bit_size())
+    }
+}
+
+/// The order of the bytes of each word when the bits are serialised
+pub trait ByteOrder {
+    /// Returns the position, counting from the least significant byte, of the byte written
+    /// in the `index` position among the `count` bytes of a word.
+    fn byte_index(index: usize, count: usize) -> usize;
+}
+
+/// The most significant byte of each word is written first
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigEndian;
+
+/// The least significant byte of each word is written first
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LittleEndian;
+
+impl ByteOrder for BigEndian {
+    fn byte_index(index: usize, count: usize) -> usize {
+        count - 1 - index
+    }
+}
+
+impl ByteOrder for LittleEndian {
+    fn byte_index(index: usize, _count: usize) -> usize {
+        index
+    }
+}
+
+/// Returns the number of bytes of a serialised word, rounding a partial byte up.
+pub(crate) fn word_bytes<T: Bitwise>() -> usize {
+    (T::bit_size() + 7) / 8
+}
+
+/// Appends the bytes of `word` to `bytes` in the byte order `E`. The unused high bits of the
+/// most significant byte are written as zeros.
+pub(crate) fn write_word<T: Bitwise, E: ByteOrder>(word: T, bytes: &mut Vec<u8>) {
+    let count = word_bytes::<T>();
+    for index in 0..count {
+        let offset = 8 * E::byte_index(index, count);
+        let width = (T::bit_size() - offset).min(8);
+        let field = word.extract_field_unchecked(offset, width);
+        bytes.push((0..width).fold(0, |byte, bit| byte | (field.get_bit_unchecked(bit) as u8) << bit));
+    }
+}
+
+/// Reads a word from the `word_bytes()` bytes of `bytes`, written in the byte order `E`. The
+/// bits beyond `bit_size()` are ignored.
+pub(crate) fn read_word<T: Bitwise, E: ByteOrder>(bytes: &[u8]) -> T {
+    let count = bytes.len();
+    bytes.iter().enumerate().fold(T::zero(), |word, (index, &byte)| {
+        let offset = 8 * E::byte_index(index, count);
+        (0..8)
+            .filter(|bit| offset + bit < T::bit_size() && byte >> bit & 1 == 1)
+            .fold(word, |word, bit| word.set_bit_unchecked(offset + bit))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_word, write_word, BigEndian, BitOrder, LittleEndian, Lsb0, Msb0};
+    use crate::{Bitwise, UInt};
+
+    #[test]
+    fn arrange() {
+        assert_eq!(Lsb0::arrange(0b0000_0110u8), 0b0000_0110);
+        assert_eq!(Msb0::arrange(0b0000_0110u8), 0b0110_0000);
+        assert_eq!(Msb0::arrange(Msb0::arrange(0x1234_5678i32)), 0x1234_5678);
+    }
+
+    #[test]
+    fn write_and_read_words() {
+        let mut bytes = Vec::new();
+        write_word::<u32, BigEndian>(0x1234_5678, &mut bytes);
+        write_word::<u32, LittleEndian>(0x1234_5678, &mut bytes);
+        write_word::<i16, BigEndian>(-2, &mut bytes);
+        assert_eq!(bytes, [0x12, 0x34, 0x56, 0x78, 0x78, 0x56, 0x34, 0x12, 0xFF, 0xFE]);
+        assert_eq!(read_word::<u32, BigEndian>(&bytes[..4]), 0x1234_5678);
+        assert_eq!(read_word::<u32, LittleEndian>(&bytes[4..8]), 0x1234_5678);
+        assert_eq!(read_word::<i16, BigEndian>(&bytes[8..]), -2);
+    }
+
+    #[test]
+    fn words_with_a_partial_byte() {
+        let mut bytes = Vec::new();
+        let word = UInt::<12>::zero().set_all();
+        write_word::<_, BigEndian>(word, &mut bytes);
+        write_word::<_, LittleEndian>(word, &mut bytes);
+        write_word::<_, BigEndian>(UInt::<3>::new(0b101).unwrap(), &mut bytes);
+        assert_eq!(bytes, [0x0F, 0xFF, 0xFF, 0x0F, 0b101]);
+        assert_eq!(read_word::<UInt<12>, BigEndian>(&bytes[..2]), word);
+        assert_eq!(read_word::<UInt<12>, LittleEndian>(&[0xFF, 0xFF]), word);
+        assert_eq!(read_word::<UInt<3>, BigEndian>(&[0xFF]).value(), 0b111);
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Word-at-a-time searches over byte slices.
+//!
+//! The aligned middle of the slice is read as `u64` words and every byte of a word is
+//! checked at once with [`Swar::lanes_zero`]; the unaligned head and tail are checked
+//! one byte at a time.
+
+use crate::swar::{LaneWidth, Swar};
+use crate::Bitwise;
+
+/// Splits `haystack` into an unaligned head, the aligned `u64` words and an unaligned tail.
+fn split_words(haystack: &[u8]) -> (&[u8], &[u64], &[u8]) {
+    // SAFETY: every bit pattern is a valid `u64`, so reinterpreting aligned bytes is sound.
+    unsafe { haystack.align_to::<u64>() }
+}
+
+/// Turns on all the bits of the bytes of `word` that are equal to one of the `needles`.
+fn matching_bytes(word: u64, needles: &[u8]) -> u64 {
+    needles.iter().fold(0, |matches, &needle| {
+        matches | (word ^ u64::broadcast_byte(needle)).lanes_zero(LaneWidth::Eight)
+    })
+}
+
+/// Returns the index of the first byte of `haystack` that is equal to one of the `needles`.
+fn find_matching(haystack: &[u8], needles: &[u8]) -> Option<usize> {
+    let (head, words, tail) = split_words(haystack);
+    if let Some(index) = head.iter().position(|byte| needles.contains(byte)) {
+        return Some(index);
+    }
+    for (position, &word) in words.iter().enumerate() {
+        // The lowest byte of the word must hold the byte that comes first in memory.
+        let matches = matching_bytes(u64::from_le(word), needles);
+        if let Some(bit) = matches.lowest_set_bit() {
+            return Some(head.len() + 8 * position + bit / 8);
+        }
+    }
+    tail.iter()
+        .position(|byte| needles.contains(byte))
+        .map(|index| haystack.len() - tail.len() + index)
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack`
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::bytes::find_byte;
+///
+/// assert_eq!(find_byte(b"2022-12-14 INFO started", b' '), Some(10));
+/// assert_eq!(find_byte(b"2022-12-14", b' '), None);
+/// ```
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    find_matching(haystack, &[needle])
+}
+
+/// Returns the index of the first byte of `haystack` that is equal to any of the `needles`
+///
+/// **Note:** Returns `None` when `needles` is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::bytes::find_any_of;
+///
+/// assert_eq!(find_any_of(b"key=value;next", b";="), Some(3));
+/// assert_eq!(find_any_of(b"key value", b";="), None);
+/// ```
+pub fn find_any_of(haystack: &[u8], needles: &[u8]) -> Option<usize> {
+    if needles.is_empty() {
+        return None;
+    }
+    find_matching(haystack, needles)
+}
+
+/// Returns the number of occurrences of `needle` in `haystack`
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::bytes::count_byte;
+///
+/// assert_eq!(count_byte(b"one\ntwo\nthree\n", b'\n'), 3);
+/// ```
+pub fn count_byte(haystack: &[u8], needle: u8) -> usize {
+    let (head, words, tail) = split_words(haystack);
+    let broadcast = u64::broadcast_byte(needle);
+    let in_words: usize = words
+        .iter()
+        .map(|&word| (word ^ broadcast).lanes_zero(LaneWidth::Eight).count_ones() as usize / 8)
+        .sum();
+    let outside_words = head.iter().chain(tail).filter(|&&byte| byte == needle).count();
+    in_words + outside_words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_byte, find_any_of, find_byte};
+
+    fn haystack() -> Vec<u8> {
+        (0..200u32).map(|i| (i * 7 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn find_byte_at_every_offset() {
+        let data = haystack();
+        // Shifting the start of the slice moves the split between head, words and tail.
+        for start in 0..16 {
+            for end in start..data.len() {
+                let slice = &data[start..end];
+                for &needle in &[0u8, 7, 13, 99, 250, 255] {
+                    let expected = slice.iter().position(|&byte| byte == needle);
+                    assert_eq!(find_byte(slice, needle), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn find_any_of_at_every_offset() {
+        let data = haystack();
+        let needles: [&[u8]; 4] = [b"", &[42], &[200, 3, 111], &[255, 254]];
+        for start in 0..16 {
+            for end in start..data.len() {
+                let slice = &data[start..end];
+                for set in &needles {
+                    let expected = slice.iter().position(|byte| set.contains(byte));
+                    assert_eq!(find_any_of(slice, set), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn count_byte_at_every_offset() {
+        let mut data = haystack();
+        data.extend(vec![b','; 40]);
+        for start in 0..16 {
+            for end in start..data.len() {
+                let slice = &data[start..end];
+                for &needle in &[0u8, 7, b',', 255] {
+                    let expected = slice.iter().filter(|&&byte| byte == needle).count();
+                    assert_eq!(count_byte(slice, needle), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_haystack() {
+        assert_eq!(find_byte(b"", 0), None);
+        assert_eq!(find_any_of(b"", b"abc"), None);
+        assert_eq!(count_byte(b"", 0), 0);
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hilbert curve index mapping in 2D.
+//!
+//! A curve of order `n` covers the `2^n x 2^n` grid, so both the coordinates and the
+//! index use the same type and `n` can be at most half of its `bit_size()`.
+
+use crate::Bitwise;
+
+/// Returns the position of the cell `(x, y)` along the Hilbert curve of the given `order`
+///
+/// **Note:** Returns `None` when `order` is greater than half of `bit_size()` or when a
+/// coordinate does not fit in `order` bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::hilbert::hilbert_index_2d;
+///
+/// assert_eq!(hilbert_index_2d(1, 0u8, 0u8), Some(0));
+/// assert_eq!(hilbert_index_2d(1, 0u8, 1u8), Some(1));
+/// assert_eq!(hilbert_index_2d(1, 1u8, 1u8), Some(2));
+/// assert_eq!(hilbert_index_2d(1, 1u8, 0u8), Some(3));
+/// assert_eq!(hilbert_index_2d(5, 0u8, 0u8), None);
+/// ```
+pub fn hilbert_index_2d<T: Bitwise>(order: usize, x: T, y: T) -> Option<T> {
+    if order > T::bit_size() / 2 {
+        return None;
+    }
+    if x.count_ones_in_range_unchecked(order..) != 0 || y.count_ones_in_range_unchecked(order..) != 0 {
+        return None;
+    }
+    let (mut x, mut y) = (x, y);
+    let mut index = T::zero();
+    for level in (0..order).rev() {
+        let rx = x.get_bit_unchecked(level);
+        let ry = y.get_bit_unchecked(level);
+        index = index
+            .update_bit_unchecked(2 * level + 1, rx)
+            .update_bit_unchecked(2 * level, rx ^ ry);
+        let (rotated_x, rotated_y) = rotate(level, x, y, rx, ry);
+        x = rotated_x;
+        y = rotated_y;
+    }
+    Some(index)
+}
+
+/// Returns the cell `(x, y)` at the position `index` along the Hilbert curve of the given
+/// `order`. This is the inverse of `hilbert_index_2d`.
+///
+/// **Note:** Returns `None` when `order` is greater than half of `bit_size()` or when `index`
+/// does not fit in `2 * order` bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::hilbert::hilbert_coordinates_2d;
+///
+/// assert_eq!(hilbert_coordinates_2d(2, 7u16), Some((1, 2)));
+/// assert_eq!(hilbert_coordinates_2d(2, 16u16), None);
+/// ```
+pub fn hilbert_coordinates_2d<T: Bitwise>(order: usize, index: T) -> Option<(T, T)> {
+    if order > T::bit_size() / 2 || index.count_ones_in_range_unchecked(2 * order..) != 0 {
+        return None;
+    }
+    let (mut x, mut y) = (T::zero(), T::zero());
+    for level in 0..order {
+        let rx = index.get_bit_unchecked(2 * level + 1);
+        let ry = index.get_bit_unchecked(2 * level) ^ rx;
+        let (rotated_x, rotated_y) = rotate(level, x, y, rx, ry);
+        x = rotated_x.update_bit_unchecked(level, rx);
+        y = rotated_y.update_bit_unchecked(level, ry);
+    }
+    Some((x, y))
+}
+
+/// Rotates and reflects the quadrant made by the `level` least significant bits.
+fn rotate<T: Bitwise>(level: usize, x: T, y: T, rx: bool, ry: bool) -> (T, T) {
+    if ry {
+        return (x, y);
+    }
+    if rx {
+        (y.flip_range_unchecked(..level), x.flip_range_unchecked(..level))
+    } else {
+        (y, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hilbert_order_2() {
+        let expected: [(u8, u8); 16] = [
+            (0, 0), (1, 0), (1, 1), (0, 1),
+            (0, 2), (0, 3), (1, 3), (1, 2),
+            (2, 2), (2, 3), (3, 3), (3, 2),
+            (3, 1), (2, 1), (2, 0), (3, 0),
+        ];
+        for (index, &(x, y)) in expected.iter().enumerate() {
+            assert_eq!(hilbert_coordinates_2d(2, index as u8), Some((x, y)));
+            assert_eq!(hilbert_index_2d(2, x, y), Some(index as u8));
+        }
+    }
+
+    #[test]
+    fn hilbert_out_of_range() {
+        assert_eq!(hilbert_index_2d(4, 16u8, 0), None);
+        assert_eq!(hilbert_index_2d(5, 0u8, 0), None);
+        assert_eq!(hilbert_coordinates_2d(3, 64u16), None);
+        assert_eq!(hilbert_coordinates_2d(9, 0u16), None);
+        assert_eq!(hilbert_index_2d(0, 0u32, 0), Some(0));
+        assert_eq!(hilbert_coordinates_2d(0, 0u32), Some((0, 0)));
+    }
+
+    #[test]
+    fn hilbert_adjacent_indices_are_neighbors() {
+        macro_rules! check {
+            ($($t:ident),*) => {$(
+                for order in 0..=($t::bit_size() / 2).min(6) {
+                    let cells = 1usize << (2 * order);
+                    let mut seen = vec![false; cells];
+                    let mut previous: Option<($t, $t)> = None;
+                    for index in 0..cells {
+                        let (x, y) = hilbert_coordinates_2d(order, index as $t).unwrap();
+                        assert_eq!(hilbert_index_2d(order, x, y), Some(index as $t));
+                        let cell = ((x as usize) << order) | y as usize;
+                        assert!(!seen[cell]);
+                        seen[cell] = true;
+                        if let Some((px, py)) = previous {
+                            let distance = (x as i64 - px as i64).abs() + (y as i64 - py as i64).abs();
+                            assert_eq!(distance, 1);
+                        }
+                        previous = Some((x, y));
+                    }
+                }
+            )*};
+        }
+        check!(i8, i16, u8, u16, u32, u128);
+    }
+
+    #[test]
+    fn hilbert_full_order() {
+        let max = u64::MAX >> 32;
+        let index = hilbert_index_2d(32, max, 0u64).unwrap();
+        assert_eq!(index, u64::MAX);
+        assert_eq!(hilbert_coordinates_2d(32, index), Some((max, 0)));
+        assert_eq!(hilbert_coordinates_2d(64, u128::MAX), Some((u64::MAX as u128, 0)));
+    }
+}
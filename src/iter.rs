@@ -0,0 +1,622 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Iterators over the bits of a [`Bitwise`] value.
+
+use std::iter::FusedIterator;
+
+use crate::Bitwise;
+
+/// Iterator over the positions of the bits turned on, returned by [`Bitwise::iter_ones`]
+#[derive(Clone, Copy, Debug)]
+pub struct IterOnes<T> {
+    bits: T,
+}
+
+impl<T: Bitwise> IterOnes<T> {
+    pub(crate) fn new(bits: T) -> Self {
+        IterOnes { bits }
+    }
+}
+
+impl<T: Bitwise> Iterator for IterOnes<T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.bits == T::zero() {
+            return None;
+        }
+        let index = self.bits.count_trailing_zeros();
+        self.bits = self.bits.clear_bit_unchecked(index);
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones_in_range_unchecked(..);
+        (len, Some(len))
+    }
+}
+
+impl<T: Bitwise> DoubleEndedIterator for IterOnes<T> {
+    fn next_back(&mut self) -> Option<usize> {
+        if self.bits == T::zero() {
+            return None;
+        }
+        let index = T::bit_size() - 1 - self.bits.count_leading_zeros();
+        self.bits = self.bits.clear_bit_unchecked(index);
+        Some(index)
+    }
+}
+
+impl<T: Bitwise> ExactSizeIterator for IterOnes<T> {}
+
+impl<T: Bitwise> FusedIterator for IterOnes<T> {}
+
+/// Iterator over the positions of the bits turned off, returned by [`Bitwise::iter_zeros`]
+#[derive(Clone, Copy, Debug)]
+pub struct IterZeros<T> {
+    ones: IterOnes<T>,
+}
+
+impl<T: Bitwise> IterZeros<T> {
+    pub(crate) fn new(bits: T) -> Self {
+        IterZeros { ones: IterOnes::new(bits.flip()) }
+    }
+}
+
+impl<T: Bitwise> Iterator for IterZeros<T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.ones.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ones.size_hint()
+    }
+}
+
+impl<T: Bitwise> DoubleEndedIterator for IterZeros<T> {
+    fn next_back(&mut self) -> Option<usize> {
+        self.ones.next_back()
+    }
+}
+
+impl<T: Bitwise> ExactSizeIterator for IterZeros<T> {}
+
+impl<T: Bitwise> FusedIterator for IterZeros<T> {}
+
+/// Iterator over the status of every bit, from the least significant one,
+/// returned by [`Bitwise::iter_bits`]
+#[derive(Clone, Copy, Debug)]
+pub struct IterBits<T> {
+    bits: T,
+    front: usize,
+    back: usize,
+}
+
+impl<T: Bitwise> IterBits<T> {
+    pub(crate) fn new(bits: T) -> Self {
+        IterBits { bits, front: 0, back: T::bit_size() }
+    }
+}
+
+impl<T: Bitwise> Iterator for IterBits<T> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.bits.get_bit_unchecked(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T: Bitwise> DoubleEndedIterator for IterBits<T> {
+    fn next_back(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.bits.get_bit_unchecked(self.back))
+    }
+}
+
+impl<T: Bitwise> ExactSizeIterator for IterBits<T> {}
+
+impl<T: Bitwise> FusedIterator for IterBits<T> {}
+
+/// Iterator over the submasks of a value in descending order, returned by
+/// [`Bitwise::submasks`] and [`Bitwise::proper_submasks`]
+///
+/// Signed values are ordered by their bit pattern, as if they were unsigned.
+#[derive(Clone, Copy, Debug)]
+pub struct Submasks<T> {
+    mask: T,
+    next: Option<T>,
+    zero: bool,
+}
+
+impl<T: Bitwise> Submasks<T> {
+    pub(crate) fn new(mask: T) -> Self {
+        Submasks { mask, next: Some(mask), zero: true }
+    }
+
+    pub(crate) fn new_proper(mask: T) -> Self {
+        let mut submasks = Submasks::new(mask);
+        submasks.next();
+        submasks
+    }
+
+    /// Skips the empty submask, which is otherwise the last item
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Vec<u8> = 0b1010u8.proper_submasks().without_zero().collect();
+    /// assert_eq!(other, vec![0b1000, 0b0010]);
+    /// ```
+    pub fn without_zero(self) -> Self {
+        Submasks { zero: false, ..self }
+    }
+}
+
+impl<T: Bitwise> Iterator for Submasks<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.next?;
+        if current == T::zero() && !self.zero {
+            self.next = None;
+            return None;
+        }
+        self.next = if current == T::zero() {
+            None
+        } else {
+            // Same as `(current - 1) & mask`, without overflowing on signed values.
+            let lowest = current.count_trailing_zeros();
+            let below = T::get_range_mask_unchecked(..lowest);
+            Some((current.clear_bit_unchecked(lowest) | below) & self.mask)
+        };
+        Some(current)
+    }
+}
+
+impl<T: Bitwise> FusedIterator for Submasks<T> {}
+
+/// Iterator over the supermasks of a value inside a universe in ascending order,
+/// returned by [`Bitwise::supermasks_within`]
+///
+/// Signed values are ordered by their bit pattern, as if they were unsigned.
+#[derive(Clone, Copy, Debug)]
+pub struct Supermasks<T> {
+    base: T,
+    free: T,
+    next: Option<T>,
+}
+
+impl<T: Bitwise> Supermasks<T> {
+    pub(crate) fn new(base: T, universe: T) -> Self {
+        let is_subset = base & universe == base;
+        Supermasks {
+            base,
+            free: universe & !base,
+            next: if is_subset { Some(T::zero()) } else { None },
+        }
+    }
+}
+
+impl<T: Bitwise> Iterator for Supermasks<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.next?;
+        let missing = self.free & !current;
+        self.next = if missing == T::zero() {
+            None
+        } else {
+            // Same as `((current | !free) + 1) & free`, without overflowing.
+            let lowest = missing.count_trailing_zeros();
+            Some(current.clear_range_unchecked(..lowest).set_bit_unchecked(lowest))
+        };
+        Some(current | self.base)
+    }
+}
+
+impl<T: Bitwise> FusedIterator for Supermasks<T> {}
+
+/// Iterator over the numbers with exactly `k` bits turned on among the `n` least
+/// significant bits, in ascending order, returned by [`Bitwise::combinations`]
+///
+/// Signed numbers are ordered by their bit pattern, as if they were unsigned.
+#[derive(Clone, Copy, Debug)]
+pub struct Combinations<T> {
+    n: usize,
+    next: Option<T>,
+}
+
+impl<T: Bitwise> Combinations<T> {
+    pub(crate) fn new(n: usize, k: usize) -> Self {
+        let n = n.min(T::bit_size());
+        Combinations {
+            n,
+            next: if k <= n { Some(T::get_range_mask_unchecked(..k)) } else { None },
+        }
+    }
+}
+
+impl<T: Bitwise> Iterator for Combinations<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.next?;
+        let n = self.n;
+        self.next = current
+            .next_same_popcount()
+            .filter(|next| next.count_leading_zeros() >= T::bit_size() - n);
+        Some(current)
+    }
+}
+
+impl<T: Bitwise> FusedIterator for Combinations<T> {}
+
+/// Returns the binomial coefficient `C(n, k)`, or `None` if it does not fit in a `u128`.
+/// It always fits for every `n` up to `128`.
+pub(crate) fn binomial(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        // `result * (n - i)` is divisible by `i + 1`; dividing by the gcd first
+        // keeps the intermediate value from overflowing.
+        let divisor = (i + 1) as u128;
+        let gcd = gcd(result, divisor);
+        result = (result / gcd).checked_mul((n - i) as u128 / (divisor / gcd))?;
+    }
+    Some(result)
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let rest = a % b;
+        a = b;
+        b = rest;
+    }
+    a
+}
+
+pub(crate) fn rank_combination<T: Bitwise>(mask: T) -> Option<u128> {
+    mask.iter_ones()
+        .enumerate()
+        .try_fold(0u128, |rank, (j, index)| rank.checked_add(binomial(index, j + 1)?))
+}
+
+pub(crate) fn unrank_combination<T: Bitwise>(mut rank: u128, k: usize) -> Option<T> {
+    // A coefficient that does not fit in a `u128` is greater than any rank.
+    let exceeds = |n: usize, k: usize, rank: u128| binomial(n, k).map_or(true, |count| count > rank);
+    if k > T::bit_size() || !exceeds(T::bit_size(), k, rank) {
+        return None;
+    }
+    let mut mask = T::zero();
+    let mut index = T::bit_size();
+    for j in (1..=k).rev() {
+        index -= 1;
+        while exceeds(index, j, rank) {
+            index -= 1;
+        }
+        rank -= binomial(index, j)?;
+        mask = mask.set_bit_unchecked(index);
+    }
+    Some(mask)
+}
+
+/// Iterator over all the numbers made of the `n` least significant bits in reflected Gray code
+/// order, returned by [`Bitwise::gray_codes`]
+///
+/// Each item is the number together with the position of the only bit that changed from the
+/// previous item, which is `None` for the first item, zero.
+#[derive(Clone, Copy, Debug)]
+pub struct GrayCodes<T> {
+    n: usize,
+    counter: T,
+    next: Option<(T, Option<usize>)>,
+}
+
+impl<T: Bitwise> GrayCodes<T> {
+    pub(crate) fn new(n: usize) -> Self {
+        GrayCodes {
+            n: n.min(T::bit_size()),
+            counter: T::zero(),
+            next: Some((T::zero(), None)),
+        }
+    }
+}
+
+impl<T: Bitwise> Iterator for GrayCodes<T> {
+    type Item = (T, Option<usize>);
+
+    fn next(&mut self) -> Option<(T, Option<usize>)> {
+        let (current, changed) = self.next?;
+        // The bit that changes at step `k` is the number of trailing zeros of `k`,
+        // which is the lowest bit turned off of `k - 1`.
+        let index = (!self.counter).count_trailing_zeros();
+        self.next = if index >= self.n {
+            None
+        } else {
+            self.counter = self.counter.clear_range_unchecked(..index).set_bit_unchecked(index);
+            Some((current.flip_bit_unchecked(index), Some(index)))
+        };
+        Some((current, changed))
+    }
+}
+
+impl<T: Bitwise> FusedIterator for GrayCodes<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bitwise;
+
+    #[test]
+    fn iter_ones() {
+        let ones: Vec<usize> = 0b10010110u8.iter_ones().collect();
+        assert_eq!(ones, vec![1, 2, 4, 7]);
+
+        let ones: Vec<usize> = 0b10010110u8.iter_ones().rev().collect();
+        assert_eq!(ones, vec![7, 4, 2, 1]);
+
+        let ones: Vec<usize> = (-1i8).iter_ones().collect();
+        assert_eq!(ones, (0..8).collect::<Vec<usize>>());
+
+        let ones: Vec<usize> = ((1u128 << 127) | 1).iter_ones().collect();
+        assert_eq!(ones, vec![0, 127]);
+
+        assert_eq!(0u32.iter_ones().next(), None);
+    }
+
+    #[test]
+    fn iter_ones_len() {
+        let mut iter = 0b10010110u8.iter_ones();
+        assert_eq!(iter.len(), 4);
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_zeros() {
+        let zeros: Vec<usize> = 0b10010110u8.iter_zeros().collect();
+        assert_eq!(zeros, vec![0, 3, 5, 6]);
+
+        let zeros: Vec<usize> = i16::MAX.iter_zeros().rev().collect();
+        assert_eq!(zeros, vec![15]);
+
+        assert_eq!(0u64.iter_zeros().len(), 64);
+    }
+
+    #[test]
+    fn iter_bits() {
+        let bits: Vec<bool> = 0b10010110u8.iter_bits().collect();
+        assert_eq!(bits, vec![false, true, true, false, true, false, false, true]);
+
+        let mut iter = 0b10000001u8.iter_bits();
+        assert_eq!(iter.len(), 8);
+        assert_eq!(iter.next_back(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.next(), Some(true));
+    }
+
+    #[test]
+    fn iter_matches_get_bit() {
+        macro_rules! check {
+            ($($t:ident),*) => {$(
+                for &value in &[0 as $t, 1, !0, 0b0101_0110, !0 << 3, (!0 as $t) ^ 5] {
+                    let ones: Vec<usize> = (0..$t::bit_size()).filter(|&i| value.get_bit_unchecked(i)).collect();
+                    assert_eq!(value.iter_ones().collect::<Vec<usize>>(), ones);
+                    let zeros: Vec<usize> = (0..$t::bit_size()).filter(|&i| !value.get_bit_unchecked(i)).collect();
+                    assert_eq!(value.iter_zeros().collect::<Vec<usize>>(), zeros);
+                    let bits: Vec<bool> = (0..$t::bit_size()).map(|i| value.get_bit_unchecked(i)).collect();
+                    assert_eq!(value.iter_bits().collect::<Vec<bool>>(), bits);
+                }
+            )*};
+        }
+        check!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+    }
+
+    #[test]
+    fn submasks() {
+        let other: Vec<u8> = 0b1010u8.submasks().collect();
+        assert_eq!(other, vec![0b1010, 0b1000, 0b0010, 0b0000]);
+
+        let other: Vec<u8> = 0u8.submasks().collect();
+        assert_eq!(other, vec![0]);
+
+        let other: Vec<i8> = i8::MIN.submasks().collect();
+        assert_eq!(other, vec![i8::MIN, 0]);
+
+        assert_eq!((-1i16).submasks().count(), 1 << 16);
+    }
+
+    #[test]
+    fn proper_submasks() {
+        let other: Vec<u8> = 0b1010u8.proper_submasks().collect();
+        assert_eq!(other, vec![0b1000, 0b0010, 0b0000]);
+
+        assert_eq!(0u8.proper_submasks().next(), None);
+    }
+
+    #[test]
+    fn submasks_without_zero() {
+        let other: Vec<u8> = 0b1010u8.submasks().without_zero().collect();
+        assert_eq!(other, vec![0b1010, 0b1000, 0b0010]);
+
+        let other: Vec<i8> = i8::MIN.proper_submasks().without_zero().collect();
+        assert_eq!(other, vec![]);
+
+        assert_eq!(0u8.submasks().without_zero().next(), None);
+        assert_eq!((-1i16).submasks().without_zero().count(), (1 << 16) - 1);
+    }
+
+    #[test]
+    fn supermasks_within() {
+        let other: Vec<u8> = 0b0010u8.supermasks_within(0b1011).collect();
+        assert_eq!(other, vec![0b0010, 0b0011, 0b1010, 0b1011]);
+
+        let other: Vec<i8> = 0i8.supermasks_within(i8::MIN).collect();
+        assert_eq!(other, vec![0, i8::MIN]);
+
+        assert_eq!(0b0100u8.supermasks_within(0b1011).next(), None);
+        assert_eq!(0u32.supermasks_within(!0).take(5).count(), 5);
+    }
+
+    #[test]
+    fn submasks_brute_force() {
+        for mask in 0..=u8::MAX {
+            let expected: Vec<u8> = (0..=mask).rev().filter(|&sub| sub & mask == sub).collect();
+            assert_eq!(mask.submasks().collect::<Vec<u8>>(), expected);
+            assert_eq!(mask.proper_submasks().collect::<Vec<u8>>(), expected[1..].to_vec());
+            assert_eq!((mask as i8).submasks().map(|sub| sub as u8).collect::<Vec<u8>>(), expected);
+        }
+        for &mask in &[0u16, 1, 0x8001, 0xF0F0, 0x1234, 0xFFFF] {
+            let expected: Vec<u16> = (0..=mask).rev().filter(|&sub| sub & mask == sub).collect();
+            assert_eq!(mask.submasks().collect::<Vec<u16>>(), expected);
+        }
+    }
+
+    #[test]
+    fn supermasks_brute_force() {
+        for &universe in &[0u8, 0b1011, 0xF0, 0xFF] {
+            for base in 0..=u8::MAX {
+                let expected: Vec<u8> = (base..=u8::MAX)
+                    .filter(|&sup| sup & base == base && sup & universe == sup)
+                    .collect();
+                assert_eq!(base.supermasks_within(universe).collect::<Vec<u8>>(), expected);
+                let signed: Vec<u8> = (base as i8).supermasks_within(universe as i8).map(|sup| sup as u8).collect();
+                assert_eq!(signed, expected);
+            }
+        }
+        for &(base, universe) in &[(0u16, 0xFFFFu16), (0x0101, 0x8F0F), (0x1000, 0x1234)] {
+            let expected: Vec<u16> = (base..=u16::MAX)
+                .filter(|&sup| sup & base == base && sup & universe == sup)
+                .collect();
+            assert_eq!(base.supermasks_within(universe).collect::<Vec<u16>>(), expected);
+        }
+    }
+
+    #[test]
+    fn combinations() {
+        let other: Vec<u8> = u8::combinations(4, 2).collect();
+        assert_eq!(other, vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]);
+
+        let other: Vec<u8> = u8::combinations(4, 0).collect();
+        assert_eq!(other, vec![0]);
+
+        let other: Vec<i8> = i8::combinations(8, 8).collect();
+        assert_eq!(other, vec![-1]);
+
+        assert_eq!(u8::combinations(3, 4).next(), None);
+        assert_eq!(u16::combinations(100, 2).count(), 120);
+        assert_eq!(u128::combinations(128, 1).last(), Some(1 << 127));
+    }
+
+    #[test]
+    fn combinations_brute_force() {
+        for n in 0..=8 {
+            for k in 0..=9 {
+                let expected: Vec<u8> = (0..=u8::MAX)
+                    .filter(|&mask| mask.count_ones() as usize == k && (n == 8 || mask >> n == 0))
+                    .collect();
+                assert_eq!(u8::combinations(n, k).collect::<Vec<u8>>(), expected);
+                let signed: Vec<u8> = i8::combinations(n, k).map(|mask| mask as u8).collect();
+                assert_eq!(signed, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn binomial() {
+        assert_eq!(super::binomial(0, 0), Some(1));
+        assert_eq!(super::binomial(5, 2), Some(10));
+        assert_eq!(super::binomial(2, 5), Some(0));
+        assert_eq!(super::binomial(64, 32), Some(1832624140942590534));
+        assert_eq!(super::binomial(128, 64), Some(23951146041928082866135587776380551750));
+        assert_eq!(super::binomial(256, 4), Some(174792640));
+        assert_eq!(super::binomial(256, 128), None);
+    }
+
+    #[test]
+    fn rank_unrank_round_trip() {
+        for k in 0..=8 {
+            for (rank, mask) in u8::combinations(8, k).enumerate() {
+                assert_eq!(mask.rank_combination(), Some(rank as u128));
+                assert_eq!(u8::unrank_combination(rank as u128, k), Some(mask));
+                assert_eq!(i8::unrank_combination(rank as u128, k), Some(mask as i8));
+            }
+            assert_eq!(u8::unrank_combination(super::binomial(8, k).unwrap(), k), None);
+        }
+        for (rank, mask) in u16::combinations(16, 5).enumerate() {
+            assert_eq!(mask.rank_combination(), Some(rank as u128));
+            assert_eq!(u16::unrank_combination(rank as u128, 5), Some(mask));
+        }
+        assert_eq!(u8::unrank_combination(0, 9), None);
+        assert_eq!(u128::MAX.rank_combination(), Some(0));
+        assert_eq!(u128::unrank_combination(0, 128), Some(u128::MAX));
+    }
+
+    #[test]
+    fn gray_codes() {
+        let other: Vec<(u8, Option<usize>)> = u8::gray_codes(3).collect();
+        assert_eq!(
+            other,
+            vec![
+                (0b000, None),
+                (0b001, Some(0)),
+                (0b011, Some(1)),
+                (0b010, Some(0)),
+                (0b110, Some(2)),
+                (0b111, Some(0)),
+                (0b101, Some(1)),
+                (0b100, Some(0)),
+            ]
+        );
+
+        let other: Vec<(u8, Option<usize>)> = u8::gray_codes(0).collect();
+        assert_eq!(other, vec![(0, None)]);
+    }
+
+    #[test]
+    fn gray_codes_full_width() {
+        macro_rules! check {
+            ($($t:ident),*) => {$(
+                let mut seen = vec![false; 1 << $t::bit_size()];
+                let mut count = 0;
+                for (index, (mask, _)) in $t::gray_codes(100).enumerate() {
+                    assert_eq!(mask, (index as $t).to_gray());
+                    let cell = mask as usize & ((1 << $t::bit_size()) - 1);
+                    assert!(!seen[cell]);
+                    seen[cell] = true;
+                    count += 1;
+                }
+                assert_eq!(count, 1 << $t::bit_size());
+            )*};
+        }
+        check!(i8, u8, i16, u16);
+    }
+}
@@ -29,7 +29,10 @@
 //! 
 //! `i8`, `i16`, `i32`, `i64`, `i128`, `u8`, `u16`, `u32`, `u64`, `u128`
 //!
-//! *Version requirement: xbitwise supports rustc 1.31 and up.*
+//! The opt-in `bmi2` feature makes `deposit_bits` and `extract_bits` use the BMI2
+//! instructions on x86_64 when the running CPU supports them.
+//!
+//! *Version requirement: xbitwise supports rustc 1.51 and up.*
 //!
 //! ## Bug reports
 //!
@@ -41,10 +44,48 @@
 //!
 //! See [LICENSE-MIT](LICENSE-MIT)
 
+#[macro_use]
+mod macros;
+
+pub mod array;
+pub mod bitorder;
+#[cfg(feature = "u64")]
+pub mod bytes;
+pub mod hilbert;
+pub mod iter;
+pub mod morton;
+pub mod order;
+#[cfg(feature = "u64")]
+mod packed;
+mod pdep;
+pub mod permutation;
+mod slice;
+pub mod swar;
+pub mod uint;
+mod unsigned;
+mod vec;
+
+pub use array::{BitArray, BitArray256, BitArray512};
+#[cfg(feature = "u64")]
+pub use packed::{PackedIter, PackedVec};
+pub use slice::{BitSlice, BitSliceMut};
+pub use uint::UInt;
+pub use unsigned::UnsignedBitwise;
+pub use vec::BitVec;
+
+use iter::{Combinations, GrayCodes, IterBits, IterOnes, IterZeros, Submasks, Supermasks};
+use order::SetOrder;
+use std::cmp::Ordering;
 use std::ops::Bound::*;
 use std::ops::RangeBounds;
 use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Not, Shl, Shr, Sub};
 
+/// Bit manipulation methods of the integer types
+///
+/// Only the single-bit methods, `get_range_mask_unchecked`, `count_ones_in_range_unchecked`,
+/// `extract_field_unchecked`, `count_trailing_zeros`, `count_leading_zeros`,
+/// `reverse_low_bits_unchecked`, `zero`, `one` and `bit_size` have to be implemented. Every
+/// other method has a default built on them.
 pub trait Bitwise:
     Sized
     + Copy
@@ -96,7 +137,10 @@ pub trait Bitwise:
     /// let other = number.get_bit(1);
     /// assert_eq!(other, Some(true));
     /// ```
-    fn get_bit(self, index: usize) -> Option<bool>;
+    fn get_bit(self, index: usize) -> Option<bool> {
+        check_bit_index_or_return_none!(index, Self::bit_size());
+        Some(self.get_bit_unchecked(index))
+    }
 
     /// Turn on the bit in the index position
     ///
@@ -134,15 +178,18 @@ pub trait Bitwise:
     /// let other: Option<i8> = number.set_bit(2);
     /// assert_eq!(other, Some(0b00110));
     /// ```
-    fn set_bit(self, index: usize) -> Option<Self>;
+    fn set_bit(self, index: usize) -> Option<Self> {
+        check_bit_index_or_return_none!(index, Self::bit_size());
+        Some(self.set_bit_unchecked(index))
+    }
 
     /// Turns on all bits in the specified range and merges the bits that are already on
     ///
-    /// **Note:** This function does not check that the `index` is within the allowed range.
+    /// **Note:** This function does not check that the `range` is within the allowed range.
     ///
     /// **Possible values:** `integer`
     ///
-    /// **Stable:** No
+    /// **Stable:** Yes
     ///
     /// # Examples
     ///
@@ -162,16 +209,21 @@ pub trait Bitwise:
     ///
     /// let other = number.set_range_unchecked(5..=7);
     /// assert_eq!(other, 0b11100100);
+    ///
+    /// let other: u8 = 0b100.set_range_unchecked(..);
+    /// assert_eq!(other, 0b11111111);
     /// ```
-    fn set_range_unchecked<R: RangeBounds<Self>>(self, range: R) -> Self;
+    fn set_range_unchecked<R: RangeBounds<usize>>(self, range: R) -> Self {
+        self | Self::get_range_mask_unchecked(range)
+    }
 
     /// Turns on all bits in the specified range and merges the bits that are already on
     ///
-    /// **Note:** Returns `None` when the `index` is not in the allowed range.
+    /// **Note:** Returns `None` when the `range` is not in the allowed range.
     ///
     /// **Possible values:** `None`, `Some(integer)`
     ///
-    /// **Stable:** No
+    /// **Stable:** Yes
     ///
     /// # Examples
     ///
@@ -190,10 +242,18 @@ pub trait Bitwise:
     ///
     /// let other: Option<i32> = number.set_range(5..=7);
     /// assert_eq!(other, Some(0b11100100));
+    ///
+    /// let other: Option<u8> = 0b100.set_range(4..9);
+    /// assert_eq!(other, None);
     /// ```
-    fn set_range<R: RangeBounds<Self>>(self, range: R) -> Option<Self>;
+    fn set_range<R: RangeBounds<usize>>(self, range: R) -> Option<Self> {
+        check_range_or_return_none!(range, Self::bit_size());
+        Some(self.set_range_unchecked(range))
+    }
 
-    /// Turn on all the bits
+    /// Turns off all bits in the specified range
+    ///
+    /// **Note:** This function does not check that the `range` is within the allowed range.
     ///
     /// **Possible values:** `integer`
     ///
@@ -204,20 +264,40 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: u8 = 0b100.set_all();
-    /// assert_eq!(other, 0b11111111);
+    /// let other: u8 = 0b11111111.clear_range_unchecked(2..5);
+    /// assert_eq!(other, 0b11100011);
+    /// ```
+    fn clear_range_unchecked<R: RangeBounds<usize>>(self, range: R) -> Self {
+        self & !Self::get_range_mask_unchecked(range)
+    }
+
+    /// Turns off all bits in the specified range
     ///
-    /// let other: u16 = 0b100.set_all();
-    /// assert_eq!(other, 0b1111111111111111);
+    /// **Note:** Returns `None` when the `range` is not in the allowed range.
     ///
-    /// let other: u32 = 0b100.set_all();
-    /// assert_eq!(other, 0b11111111111111111111111111111111);
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b11111111.clear_range(2..=4);
+    /// assert_eq!(other, Some(0b11100011));
+    ///
+    /// let other: Option<u8> = 0b11111111.clear_range(..=8);
+    /// assert_eq!(other, None);
     /// ```
-    fn set_all(self) -> Self;
+    fn clear_range<R: RangeBounds<usize>>(self, range: R) -> Option<Self> {
+        check_range_or_return_none!(range, Self::bit_size());
+        Some(self.clear_range_unchecked(range))
+    }
 
-    /// Update the bit at position `index` with the value `new_value`
+    /// Flips all bits in the specified range
     ///
-    /// **Note:** This function does not check that the `index` is within the allowed range.
+    /// **Note:** This function does not check that the `range` is within the allowed range.
     ///
     /// **Possible values:** `integer`
     ///
@@ -228,16 +308,18 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: i8 = 0b00010.update_bit_unchecked(4, true);
-    /// assert_eq!(other, 0b10010);
+    /// let other: u8 = 0b00001111.flip_range_unchecked(2..6);
+    /// assert_eq!(other, 0b00110011);
     /// ```
-    fn update_bit_unchecked(self, index: usize, new_value: bool) -> Self;
+    fn flip_range_unchecked<R: RangeBounds<usize>>(self, range: R) -> Self {
+        self ^ Self::get_range_mask_unchecked(range)
+    }
 
-    /// Update the bit at position `index` with the value `new_value`
+    /// Flips all bits in the specified range
     ///
-    /// **Note:** Returns `None` when the `index` is not in the allowed range.
+    /// **Note:** Returns `None` when the `range` is not in the allowed range.
     ///
-    /// **Possible values:** `integer`
+    /// **Possible values:** `None`, `Some(integer)`
     ///
     /// **Stable:** Yes
     ///
@@ -246,14 +328,20 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: Option<i8> = 0b00010.update_bit(4, true);
-    /// assert_eq!(other, Some(0b10010));
+    /// let other: Option<u8> = 0b00001111.flip_range(2..6);
+    /// assert_eq!(other, Some(0b00110011));
+    ///
+    /// let other: Option<u8> = 0b00001111.flip_range(6..2);
+    /// assert_eq!(other, None);
     /// ```
-    fn update_bit(self, index: usize, new_value: bool) -> Option<Self>;
+    fn flip_range<R: RangeBounds<usize>>(self, range: R) -> Option<Self> {
+        check_range_or_return_none!(range, Self::bit_size());
+        Some(self.flip_range_unchecked(range))
+    }
 
-    /// Turn off the bit in the index position
+    /// Returns a number with only the bits in the specified range turned on
     ///
-    /// **Note:** This function does not check that the `index` is within the allowed range.
+    /// **Note:** This function does not check that the `range` is within the allowed range.
     ///
     /// **Possible values:** `integer`
     ///
@@ -264,16 +352,17 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: i8 = 0b00010001.clear_bit_unchecked(4);
-    /// assert_eq!(other, 0b00000001);
+    /// assert_eq!(u8::get_range_mask_unchecked(2..5), 0b00011100);
+    /// assert_eq!(u8::get_range_mask_unchecked(..), 0b11111111);
+    /// assert_eq!(u8::get_range_mask_unchecked(3..3), 0);
     /// ```
-    fn clear_bit_unchecked(self, index: usize) -> Self;
+    fn get_range_mask_unchecked<R: RangeBounds<usize>>(range: R) -> Self;
 
-    /// Turn off the bit in the index position
+    /// Returns a number with only the bits in the specified range turned on
     ///
-    /// **Note:** Returns `None` when the `index` is not in the allowed range.
+    /// **Note:** Returns `None` when the `range` is not in the allowed range.
     ///
-    /// **Possible values:** `integer`
+    /// **Possible values:** `None`, `Some(integer)`
     ///
     /// **Stable:** Yes
     ///
@@ -282,12 +371,18 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: Option<i8> = 0b00010001.clear_bit(4);
-    /// assert_eq!(other, Some(0b00000001));
+    /// assert_eq!(u8::get_range_mask(2..5), Some(0b00011100));
+    /// assert_eq!(i8::get_range_mask(4..), Some(0b11110000u8 as i8));
+    /// assert_eq!(u8::get_range_mask(2..9), None);
     /// ```
-    fn clear_bit(self, index: usize) -> Option<Self>;
+    fn get_range_mask<R: RangeBounds<usize>>(range: R) -> Option<Self> {
+        check_range_or_return_none!(range, Self::bit_size());
+        Some(Self::get_range_mask_unchecked(range))
+    }
 
-    /// Turn off all bits
+    /// Returns the number of bits turned on in the specified range
+    ///
+    /// **Note:** This function does not check that the `range` is within the allowed range.
     ///
     /// **Possible values:** `integer`
     ///
@@ -298,16 +393,16 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: i8 = 0b00010001.clear();
-    /// assert_eq!(other, 0b00000000);
+    /// let other: usize = 0b10110110u8.count_ones_in_range_unchecked(2..6);
+    /// assert_eq!(other, 3);
     /// ```
-    fn clear(self) -> Self;
+    fn count_ones_in_range_unchecked<R: RangeBounds<usize>>(self, range: R) -> usize;
 
-    /// Flips the bit at the index position
+    /// Returns the number of bits turned on in the specified range
     ///
-    /// **Note:** This function does not check that the `index` is within the allowed range.
+    /// **Note:** Returns `None` when the `range` is not in the allowed range.
     ///
-    /// **Possible values:** `integer`
+    /// **Possible values:** `None`, `Some(integer)`
     ///
     /// **Stable:** Yes
     ///
@@ -316,14 +411,20 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: i8 = 0b00010001.flip_bit_unchecked(2);
-    /// assert_eq!(other, 0b00010101);
+    /// let other: Option<usize> = 0b10110110u8.count_ones_in_range(..);
+    /// assert_eq!(other, Some(5));
+    ///
+    /// let other: Option<usize> = 0b10110110u8.count_ones_in_range(..=8);
+    /// assert_eq!(other, None);
     /// ```
-    fn flip_bit_unchecked(self, index: usize) -> Self;
+    fn count_ones_in_range<R: RangeBounds<usize>>(self, range: R) -> Option<usize> {
+        check_range_or_return_none!(range, Self::bit_size());
+        Some(self.count_ones_in_range_unchecked(range))
+    }
 
-    /// Flips the bit at the index position
+    /// Update all bits in the specified range with the value `new_value`
     ///
-    /// **Note:** Returns `None` when the `index` is not in the allowed range.
+    /// **Note:** This function does not check that the `range` is within the allowed range.
     ///
     /// **Possible values:** `integer`
     ///
@@ -334,14 +435,21 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: Option<i8> = 0b00010001.flip_bit(2);
-    /// assert_eq!(other, Some(0b00010101));
+    /// let other: u8 = 0b00000000.update_range_unchecked(1..3, true);
+    /// assert_eq!(other, 0b00000110);
+    ///
+    /// let other: u8 = 0b11111111.update_range_unchecked(1..3, false);
+    /// assert_eq!(other, 0b11111001);
     /// ```
-    fn flip_bit(self, index: usize) -> Option<Self>;
+    fn update_range_unchecked<R: RangeBounds<usize>>(self, range: R, new_value: bool) -> Self {
+        if new_value { self.set_range_unchecked(range) } else { self.clear_range_unchecked(range) }
+    }
 
-    /// Flips all the bits
+    /// Update all bits in the specified range with the value `new_value`
     ///
-    /// **Possible values:** `integer`
+    /// **Note:** Returns `None` when the `range` is not in the allowed range.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
     ///
     /// **Stable:** Yes
     ///
@@ -350,15 +458,22 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// #[allow(overflowing_literals)]
-    /// let other: i8 =   0b10010001.flip();
-    /// assert_eq!(other, 0b01101110);
+    /// let other: Option<u8> = 0b00000000.update_range(1..3, true);
+    /// assert_eq!(other, Some(0b00000110));
+    ///
+    /// let other: Option<u8> = 0b00000000.update_range(1..10, true);
+    /// assert_eq!(other, None);
     /// ```
-    fn flip(self) -> Self;
+    fn update_range<R: RangeBounds<usize>>(self, range: R, new_value: bool) -> Option<Self> {
+        check_range_or_return_none!(range, Self::bit_size());
+        Some(self.update_range_unchecked(range, new_value))
+    }
 
-    /// Returns the parity of x, i.e. the number of 1-bits in x modulo 2.
+    /// Gets the `width` bits starting at the `offset` position, moved to the least significant bits
     ///
-    /// **Possible values:** `true` or `false`
+    /// **Note:** This function does not check that the field is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
     ///
     /// **Stable:** Yes
     ///
@@ -367,18 +482,16 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: bool = 0b00000001.parity();
-    /// assert_eq!(other, true);
-    ///
-    /// let other: bool = 0b10000001.parity();
-    /// assert_eq!(other, false);
+    /// let other: u16 = 0b1011_0110_0000.extract_field_unchecked(5, 4);
+    /// assert_eq!(other, 0b1011);
     /// ```
-    fn parity(self) -> bool;
+    fn extract_field_unchecked(self, offset: usize, width: usize) -> Self;
 
-    /// Returns a number with the hamming distance, which is the number of positions at which
-    /// the corresponding bits are different.
-    /// 
-    /// **Possible values:** `true` or `false`
+    /// Gets the `width` bits starting at the `offset` position, moved to the least significant bits
+    ///
+    /// **Note:** Returns `None` when the field is not in the allowed range.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
     ///
     /// **Stable:** Yes
     ///
@@ -387,15 +500,24 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let other: usize = 0b00000000.hamming_distance(0b11000001);
-    /// assert_eq!(other, 3);
+    /// let other: Option<u16> = 0b1011_0110_0000.extract_field(5, 4);
+    /// assert_eq!(other, Some(0b1011));
+    ///
+    /// let other: Option<u16> = 0b1011_0110_0000.extract_field(14, 4);
+    /// assert_eq!(other, None);
     /// ```
-    fn hamming_distance(self, other: Self) -> usize;
+    fn extract_field(self, offset: usize, width: usize) -> Option<Self> {
+        check_field_or_return_none!(offset, width, Self::bit_size());
+        Some(self.extract_field_unchecked(offset, width))
+    }
 
-    /// Return a number with all bits off (an integer of value zero).
-    /// 
-    /// **Possible values:** `0`
-    /// 
+    /// Gets the `width` bits starting at the `offset` position and sign-extends them
+    /// from the most significant bit of the field
+    ///
+    /// **Note:** This function does not check that the field is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
     /// **Stable:** Yes
     ///
     /// # Examples
@@ -403,14 +525,27 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// assert_eq!(i8::zero(), 0i8);
+    /// let other: i16 = 0b1011_0110_0000.extract_field_signed_unchecked(5, 4);
+    /// assert_eq!(other, -5);
+    ///
+    /// let other: i16 = 0b0110_0110_0000.extract_field_signed_unchecked(5, 4);
+    /// assert_eq!(other, 3);
     /// ```
-    fn zero() -> Self;
+    fn extract_field_signed_unchecked(self, offset: usize, width: usize) -> Self {
+        let field = self.extract_field_unchecked(offset, width);
+        if width == 0 || !field.get_bit_unchecked(width - 1) {
+            return field;
+        }
+        field | !Self::get_range_mask_unchecked(..width)
+    }
 
-    /// Returns a number with the least significant bit on (an integer with value one).
+    /// Gets the `width` bits starting at the `offset` position and sign-extends them
+    /// from the most significant bit of the field
+    ///
+    /// **Note:** Returns `None` when the field is not in the allowed range.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
     ///
-    /// **Possible values:** `1`
-    /// 
     /// **Stable:** Yes
     ///
     /// # Examples
@@ -418,14 +553,28 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// assert_eq!(i8::one(), 1i8);
+    /// let other: Option<i16> = 0b1011_0110_0000.extract_field_signed(5, 4);
+    /// assert_eq!(other, Some(-5));
+    ///
+    /// let other: Option<u8> = 0b0111_0000.extract_field_signed(4, 3);
+    /// assert_eq!(other, Some(0b1111_1111));
+    ///
+    /// let other: Option<i16> = 0b1011_0110_0000.extract_field_signed(5, 12);
+    /// assert_eq!(other, None);
     /// ```
-    fn one() -> Self;
+    fn extract_field_signed(self, offset: usize, width: usize) -> Option<Self> {
+        check_field_or_return_none!(offset, width, Self::bit_size());
+        Some(self.extract_field_signed_unchecked(offset, width))
+    }
 
-    /// Returns the number of bits of the number
+    /// Replaces the `width` bits starting at the `offset` position with the least
+    /// significant bits of `value`
+    ///
+    /// **Note:** This function does not check that the field is within the allowed range.
+    /// The bits of `value` that do not fit in `width` are ignored.
     ///
     /// **Possible values:** `integer`
-    /// 
+    ///
     /// **Stable:** Yes
     ///
     /// # Examples
@@ -433,123 +582,1971 @@ pub trait Bitwise:
     /// ```rust
     /// use xbitwise::Bitwise;
     ///
-    /// let expected: usize = 8;
-    /// assert_eq!(i8::bit_size(), 8usize);
+    /// let other: u16 = 0b1111_1111_1111.insert_field_unchecked(4, 4, 0b0101);
+    /// assert_eq!(other, 0b1111_0101_1111);
     /// ```
-    fn bit_size() -> usize;
-}
-
-macro_rules! check_bit_index_or_return_none {
-    ($bit:expr, $max_bits: expr) => {
-        if $bit >= $max_bits {
-            return None;
+    fn insert_field_unchecked(self, offset: usize, width: usize, value: Self) -> Self {
+        if width == 0 {
+            return self;
         }
-    };
-}
-
-macro_rules! impl_bitwise {
-    ($($max_bits:expr => $t:ident),*) => {$(
-
-        impl Bitwise for $t {
-
-            fn get_bit_unchecked(self, index: usize) -> bool {
-                let mask = (Self::one() << index);
-                (self & mask) == mask
-            }
-
-            fn get_bit(self, index: usize) -> Option<bool> {
-                check_bit_index_or_return_none!(index, $max_bits);
-                Some(self.get_bit_unchecked(index))
-            }
+        let field = value & Self::get_range_mask_unchecked(..width);
+        self.clear_range_unchecked(offset..offset + width) | (field << offset as u32)
+    }
 
-            fn set_bit_unchecked(self, index: usize) -> Self {
-                self | (Self::one() << index)
-            }
+    /// Replaces the `width` bits starting at the `offset` position with the least
+    /// significant bits of `value`
+    ///
+    /// **Note:** Returns `None` when the field is not in the allowed range.
+    /// The bits of `value` that do not fit in `width` are ignored.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u16> = 0b1111_1111_1111.insert_field(4, 4, 0b1_0101);
+    /// assert_eq!(other, Some(0b1111_0101_1111));
+    ///
+    /// let other: Option<u16> = 0.insert_field(12, 5, 1);
+    /// assert_eq!(other, None);
+    /// ```
+    fn insert_field(self, offset: usize, width: usize, value: Self) -> Option<Self> {
+        check_field_or_return_none!(offset, width, Self::bit_size());
+        Some(self.insert_field_unchecked(offset, width, value))
+    }
 
-            fn set_bit(self, index: usize) -> Option<Self> {
-                check_bit_index_or_return_none!(index, $max_bits);
-                Some(self.set_bit_unchecked(index))
-            }
+    /// Turn on all the bits
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b100.set_all();
+    /// assert_eq!(other, 0b11111111);
+    ///
+    /// let other: u16 = 0b100.set_all();
+    /// assert_eq!(other, 0b1111111111111111);
+    ///
+    /// let other: u32 = 0b100.set_all();
+    /// assert_eq!(other, 0b11111111111111111111111111111111);
+    /// ```
+    fn set_all(self) -> Self {
+        self.clear().flip()
+    }
 
-            fn set_range_unchecked<R: RangeBounds<Self>>(self, range: R) -> Self {
-                let left = match range.start_bound() {
-                    Included(val) => (*val) as Self,
-                    Excluded(val) => (*val) + 1 as Self,
-                    _ => 0,
-                };
-
-                let right = match range.end_bound() {
-                    Included(val) => (*val) as Self,
-                    Excluded(val) => (*val) - 1 as Self,
-                    _ => ($max_bits as Self) - 2,
-                };
-
-                let range = (((1 << left) - 1) ^ ((1 << right) - 1)) | (1 << right);
-                self | range
-            }
+    /// Update the bit at position `index` with the value `new_value`
+    ///
+    /// **Note:** This function does not check that the `index` is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: i8 = 0b00010.update_bit_unchecked(4, true);
+    /// assert_eq!(other, 0b10010);
+    /// ```
+    fn update_bit_unchecked(self, index: usize, new_value: bool) -> Self {
+        if new_value { self.set_bit_unchecked(index) } else { self.clear_bit_unchecked(index) }
+    }
 
-            fn set_range<R: RangeBounds<Self>>(self, range: R) -> Option<Self> {
-                let left = match range.start_bound() {
-                    Included(val) => (*val) as usize,
-                    Excluded(val) => ((*val) + 1) as usize,
-                    _ => 0,
-                };
-
-                let right = match range.end_bound() {
-                    Included(val) => (*val) as usize,
-                    Excluded(val) => ((*val) - 1) as usize,
-                    _ => ($max_bits as usize) - 2 as usize,
-                };
-                check_bit_index_or_return_none!(left, $max_bits);
-                check_bit_index_or_return_none!(right, $max_bits);
-                Some(self.set_range_unchecked(range))
-            }
+    /// Update the bit at position `index` with the value `new_value`
+    ///
+    /// **Note:** Returns `None` when the `index` is not in the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<i8> = 0b00010.update_bit(4, true);
+    /// assert_eq!(other, Some(0b10010));
+    /// ```
+    fn update_bit(self, index: usize, new_value: bool) -> Option<Self> {
+        check_bit_index_or_return_none!(index, Self::bit_size());
+        Some(self.update_bit_unchecked(index, new_value))
+    }
 
-            fn set_all(self) -> Self {
-                self.clear().flip()
-            }
+    /// Turn off the bit in the index position
+    ///
+    /// **Note:** This function does not check that the `index` is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: i8 = 0b00010001.clear_bit_unchecked(4);
+    /// assert_eq!(other, 0b00000001);
+    /// ```
+    fn clear_bit_unchecked(self, index: usize) -> Self;
 
-            fn update_bit_unchecked(self, index: usize, new_value: bool) -> Self {
-                if new_value { self.set_bit_unchecked(index) } else { self.clear_bit_unchecked(index) }
-            }
+    /// Turn off the bit in the index position
+    ///
+    /// **Note:** Returns `None` when the `index` is not in the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<i8> = 0b00010001.clear_bit(4);
+    /// assert_eq!(other, Some(0b00000001));
+    /// ```
+    fn clear_bit(self, index: usize) -> Option<Self> {
+        check_bit_index_or_return_none!(index, Self::bit_size());
+        Some(self.clear_bit_unchecked(index))
+    }
+
+    /// Turn off all bits
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: i8 = 0b00010001.clear();
+    /// assert_eq!(other, 0b00000000);
+    /// ```
+    fn clear(self) -> Self {
+        Self::zero()
+    }
+
+    /// Flips the bit at the index position
+    ///
+    /// **Note:** This function does not check that the `index` is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: i8 = 0b00010001.flip_bit_unchecked(2);
+    /// assert_eq!(other, 0b00010101);
+    /// ```
+    fn flip_bit_unchecked(self, index: usize) -> Self;
+
+    /// Flips the bit at the index position
+    ///
+    /// **Note:** Returns `None` when the `index` is not in the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<i8> = 0b00010001.flip_bit(2);
+    /// assert_eq!(other, Some(0b00010101));
+    /// ```
+    fn flip_bit(self, index: usize) -> Option<Self> {
+        check_bit_index_or_return_none!(index, Self::bit_size());
+        Some(self.flip_bit_unchecked(index))
+    }
+
+    /// Flips all the bits
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// #[allow(overflowing_literals)]
+    /// let other: i8 =   0b10010001.flip();
+    /// assert_eq!(other, 0b01101110);
+    /// ```
+    fn flip(self) -> Self {
+        !self
+    }
+
+    /// Returns the parity of x, i.e. the number of 1-bits in x modulo 2.
+    ///
+    /// **Possible values:** `true` or `false`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: bool = 0b00000001.parity();
+    /// assert_eq!(other, true);
+    ///
+    /// let other: bool = 0b10000001.parity();
+    /// assert_eq!(other, false);
+    /// ```
+    fn parity(self) -> bool {
+        self.count_ones_in_range_unchecked(..) % 2 == 1
+    }
+
+    /// Returns a number with the hamming distance, which is the number of positions at which
+    /// the corresponding bits are different.
+    /// 
+    /// **Possible values:** `true` or `false`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: usize = 0b00000000.hamming_distance(0b11000001);
+    /// assert_eq!(other, 3);
+    /// ```
+    fn hamming_distance(self, other: Self) -> usize {
+        (self ^ other).count_ones_in_range_unchecked(..)
+    }
+
+    /// Returns the number of consecutive bits turned off, starting from the least significant bit
+    ///
+    /// **Possible values:** `integer` between `0` and `bit_size()`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert_eq!(0b01011000u8.count_trailing_zeros(), 3);
+    /// assert_eq!(0u8.count_trailing_zeros(), 8);
+    /// ```
+    fn count_trailing_zeros(self) -> usize;
+
+    /// Returns the number of consecutive bits turned off, starting from the most significant bit
+    ///
+    /// **Possible values:** `integer` between `0` and `bit_size()`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert_eq!(0b01011000u8.count_leading_zeros(), 1);
+    /// assert_eq!(0i16.count_leading_zeros(), 16);
+    /// ```
+    fn count_leading_zeros(self) -> usize;
+
+    /// Returns an iterator over the positions of the bits turned on, in increasing order
+    ///
+    /// **Note:** Each step jumps directly to the next bit turned on, so the cost is
+    /// proportional to the number of bits turned on.
+    ///
+    /// **Possible values:** `IterOnes`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Vec<usize> = 0b10010110u8.iter_ones().collect();
+    /// assert_eq!(other, vec![1, 2, 4, 7]);
+    ///
+    /// let other: Vec<usize> = 0b10010110u8.iter_ones().rev().collect();
+    /// assert_eq!(other, vec![7, 4, 2, 1]);
+    /// ```
+    fn iter_ones(self) -> IterOnes<Self> {
+        IterOnes::new(self)
+    }
+
+    /// Returns an iterator over the positions of the bits turned off, in increasing order
+    ///
+    /// **Possible values:** `IterZeros`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Vec<usize> = 0b10010110u8.iter_zeros().collect();
+    /// assert_eq!(other, vec![0, 3, 5, 6]);
+    /// ```
+    fn iter_zeros(self) -> IterZeros<Self> {
+        IterZeros::new(self)
+    }
+
+    /// Returns an iterator over the status of every bit, starting from the least significant bit
+    ///
+    /// **Possible values:** `IterBits`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Vec<bool> = 0b1001u8.iter_bits().take(4).collect();
+    /// assert_eq!(other, vec![true, false, false, true]);
+    /// ```
+    fn iter_bits(self) -> IterBits<Self> {
+        IterBits::new(self)
+    }
+
+    /// Returns an iterator over all the submasks of the number, from the number itself down to zero
+    ///
+    /// **Note:** Zero is always the last submask, so use `proper_submasks` to skip the number
+    /// itself and `Submasks::without_zero` to skip zero. Signed numbers are ordered by their
+    /// bit pattern, as if they were unsigned.
+    ///
+    /// **Possible values:** `Submasks`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Vec<u8> = 0b1010u8.submasks().collect();
+    /// assert_eq!(other, vec![0b1010, 0b1000, 0b0010, 0b0000]);
+    /// ```
+    fn submasks(self) -> Submasks<Self> {
+        Submasks::new(self)
+    }
+
+    /// Returns an iterator over all the submasks of the number except the number itself,
+    /// in descending order
+    ///
+    /// **Possible values:** `Submasks`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Vec<u8> = 0b1010u8.proper_submasks().collect();
+    /// assert_eq!(other, vec![0b1000, 0b0010, 0b0000]);
+    /// ```
+    fn proper_submasks(self) -> Submasks<Self> {
+        Submasks::new_proper(self)
+    }
+
+    /// Returns an iterator over all the numbers that contain the bits of the number and are
+    /// contained in `universe`, in ascending order
+    ///
+    /// **Note:** The iterator is empty when the number is not contained in `universe`.
+    ///
+    /// **Possible values:** `Supermasks`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Vec<u8> = 0b0010u8.supermasks_within(0b1011).collect();
+    /// assert_eq!(other, vec![0b0010, 0b0011, 0b1010, 0b1011]);
+    /// ```
+    fn supermasks_within(self, universe: Self) -> Supermasks<Self> {
+        Supermasks::new(self, universe)
+    }
+
+    /// Returns the next greater number with the same number of bits turned on
+    ///
+    /// **Note:** Returns `None` when there is no greater number with the same number of bits
+    /// turned on. Signed numbers are ordered by their bit pattern, as if they were unsigned.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b00101100.next_same_popcount();
+    /// assert_eq!(other, Some(0b00110001));
+    ///
+    /// let other: Option<u8> = 0b11100000.next_same_popcount();
+    /// assert_eq!(other, None);
+    /// ```
+    fn next_same_popcount(self) -> Option<Self> {
+        if self == Self::zero() {
+            return None;
+        }
+        let bits = Self::bit_size();
+        let lowest = self.count_trailing_zeros();
+        let run = (!self.extract_field_unchecked(lowest, bits - lowest)).count_trailing_zeros();
+        let moved = lowest + run;
+        if moved >= bits {
+            return None;
+        }
+        let rest = Self::get_range_mask_unchecked(..run - 1);
+        Some(self.clear_range_unchecked(lowest..moved).set_bit_unchecked(moved) | rest)
+    }
+
+    /// Returns an iterator over all the numbers with exactly `k` bits turned on among
+    /// the `n` least significant bits, in ascending order
+    ///
+    /// **Note:** When `n` is greater than `bit_size()` all the bits are used. Signed numbers
+    /// are ordered by their bit pattern, as if they were unsigned.
+    ///
+    /// **Possible values:** `Combinations`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Vec<u8> = u8::combinations(4, 2).collect();
+    /// assert_eq!(other, vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]);
+    /// ```
+    fn combinations(n: usize, k: usize) -> Combinations<Self> {
+        Combinations::new(n, k)
+    }
+
+    /// Returns the position of the number among all the numbers with the same number of bits
+    /// turned on, using the combinatorial number system
+    ///
+    /// **Note:** Returns `None` when the position does not fit in a `u128`, which can only
+    /// happen for numbers wider than 128 bits.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert_eq!(0b0011u8.rank_combination(), Some(0));
+    /// assert_eq!(0b1010u8.rank_combination(), Some(4));
+    /// ```
+    fn rank_combination(self) -> Option<u128> {
+        iter::rank_combination(self)
+    }
+
+    /// Returns the number with `k` bits turned on at the position `rank`, using the
+    /// combinatorial number system. This is the inverse of `rank_combination`.
+    ///
+    /// **Note:** Returns `None` when `rank` is not lower than the number of combinations.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert_eq!(u8::unrank_combination(4, 2), Some(0b1010));
+    /// assert_eq!(u8::unrank_combination(28, 2), None);
+    /// ```
+    fn unrank_combination(rank: u128, k: usize) -> Option<Self> {
+        iter::unrank_combination(rank, k)
+    }
+
+    /// Returns the position of the least significant bit turned on
+    ///
+    /// **Note:** Returns `None` when all the bits are turned off.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 0b01011000u8.lowest_set_bit();
+    /// assert_eq!(other, Some(3));
+    ///
+    /// let other: Option<usize> = 0u8.lowest_set_bit();
+    /// assert_eq!(other, None);
+    /// ```
+    fn lowest_set_bit(self) -> Option<usize> {
+        if self == Self::zero() {
+            return None;
+        }
+        Some(self.count_trailing_zeros())
+    }
+
+    /// Returns the position of the most significant bit turned on
+    ///
+    /// **Note:** Returns `None` when all the bits are turned off.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 0b01011000u8.highest_set_bit();
+    /// assert_eq!(other, Some(6));
+    ///
+    /// let other: Option<usize> = (-1i16).highest_set_bit();
+    /// assert_eq!(other, Some(15));
+    /// ```
+    fn highest_set_bit(self) -> Option<usize> {
+        if self == Self::zero() {
+            return None;
+        }
+        Some(Self::bit_size() - 1 - self.count_leading_zeros())
+    }
+
+    /// Returns the position of the first bit turned on at or after the `from` position
+    ///
+    /// **Note:** Returns `None` when there is no bit turned on at or after `from`.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 0b01011000u8.next_set_bit(4);
+    /// assert_eq!(other, Some(4));
+    ///
+    /// let other: Option<usize> = 0b01011000u8.next_set_bit(5);
+    /// assert_eq!(other, Some(6));
+    ///
+    /// let other: Option<usize> = 0b01011000u8.next_set_bit(7);
+    /// assert_eq!(other, None);
+    /// ```
+    fn next_set_bit(self, from: usize) -> Option<usize> {
+        check_bit_index_or_return_none!(from, Self::bit_size());
+        self.clear_range_unchecked(..from).lowest_set_bit()
+    }
+
+    /// Returns the position of the last bit turned on at or before the `from` position
+    ///
+    /// **Note:** Returns `None` when there is no bit turned on at or before `from`.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 0b01011000u8.prev_set_bit(5);
+    /// assert_eq!(other, Some(4));
+    ///
+    /// let other: Option<usize> = 0b01011000u8.prev_set_bit(2);
+    /// assert_eq!(other, None);
+    ///
+    /// let other: Option<usize> = 0b01011000u8.prev_set_bit(100);
+    /// assert_eq!(other, Some(6));
+    /// ```
+    fn prev_set_bit(self, from: usize) -> Option<usize> {
+        let end = from.saturating_add(1).min(Self::bit_size());
+        self.clear_range_unchecked(end..).highest_set_bit()
+    }
+
+    /// Returns the position of the least significant bit turned off
+    ///
+    /// **Note:** Returns `None` when all the bits are turned on.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 0b00000111u8.lowest_clear_bit();
+    /// assert_eq!(other, Some(3));
+    ///
+    /// let other: Option<usize> = u8::MAX.lowest_clear_bit();
+    /// assert_eq!(other, None);
+    /// ```
+    fn lowest_clear_bit(self) -> Option<usize> {
+        (!self).lowest_set_bit()
+    }
+
+    /// Returns the position of the most significant bit turned off
+    ///
+    /// **Note:** Returns `None` when all the bits are turned on.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 0b10011111u8.highest_clear_bit();
+    /// assert_eq!(other, Some(6));
+    ///
+    /// let other: Option<usize> = 0i32.highest_clear_bit();
+    /// assert_eq!(other, Some(31));
+    /// ```
+    fn highest_clear_bit(self) -> Option<usize> {
+        (!self).highest_set_bit()
+    }
+
+    /// Returns the position of the first bit turned off at or after the `from` position
+    ///
+    /// **Note:** Returns `None` when there is no bit turned off at or after `from`.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 0b11100111u8.next_clear_bit(1);
+    /// assert_eq!(other, Some(3));
+    ///
+    /// let other: Option<usize> = 0b11100111u8.next_clear_bit(5);
+    /// assert_eq!(other, None);
+    /// ```
+    fn next_clear_bit(self, from: usize) -> Option<usize> {
+        (!self).next_set_bit(from)
+    }
+
+    /// Returns the position of the last bit turned off at or before the `from` position
+    ///
+    /// **Note:** Returns `None` when there is no bit turned off at or before `from`.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 0b11100111u8.prev_clear_bit(7);
+    /// assert_eq!(other, Some(4));
+    ///
+    /// let other: Option<usize> = 0b11100111u8.prev_clear_bit(2);
+    /// assert_eq!(other, None);
+    /// ```
+    fn prev_clear_bit(self, from: usize) -> Option<usize> {
+        (!self).prev_set_bit(from)
+    }
+
+    /// Returns the position of the `n`-th bit turned on, counting from zero and starting from the least significant bit
+    ///
+    /// **Note:** Returns `None` when there are not more than `n` bits turned on.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 0b01011000u8.select(0);
+    /// assert_eq!(other, Some(3));
+    ///
+    /// let other: Option<usize> = 0b01011000u8.select(2);
+    /// assert_eq!(other, Some(6));
+    ///
+    /// let other: Option<usize> = 0b01011000u8.select(3);
+    /// assert_eq!(other, None);
+    /// ```
+    fn select(self, n: usize) -> Option<usize> {
+        self.iter_ones().nth(n)
+    }
+
+    /// Deposits the least significant bits of the number into the positions of the bits
+    /// turned on in `mask`, like the x86 BMI2 `PDEP` instruction
+    ///
+    /// **Note:** With the `bmi2` feature the hardware instruction is used on x86_64 when the
+    /// running CPU supports it. The standard library may add an inherent method with this name,
+    /// so prefer calling it through the trait.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = Bitwise::deposit_bits(0b101, 0b11010010);
+    /// assert_eq!(other, 0b01000010);
+    /// ```
+    fn deposit_bits(self, mask: Self) -> Self {
+        pdep::deposit_bits(self, mask)
+    }
+
+    /// Extracts the bits of the number in the positions of the bits turned on in `mask` and
+    /// packs them into the least significant bits, like the x86 BMI2 `PEXT` instruction
+    ///
+    /// **Note:** With the `bmi2` feature the hardware instruction is used on x86_64 when the
+    /// running CPU supports it. The standard library may add an inherent method with this name,
+    /// so prefer calling it through the trait.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = Bitwise::extract_bits(0b01000010, 0b11010010);
+    /// assert_eq!(other, 0b101);
+    /// ```
+    fn extract_bits(self, mask: Self) -> Self {
+        pdep::extract_bits(self, mask)
+    }
+
+    /// Reverses the order of the bits in the specified range, leaving the other bits unchanged
+    ///
+    /// **Note:** This function does not check that the `range` is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b1100_0101.reverse_bits_in_range_unchecked(2..6);
+    /// assert_eq!(other, 0b1110_0001);
+    /// ```
+    fn reverse_bits_in_range_unchecked<R: RangeBounds<usize>>(self, range: R) -> Self {
+        let (start, end) = range_to_bounds(&range, Self::bit_size());
+        if start >= end {
+            return self;
+        }
+        let width = end - start;
+        let reversed = self.extract_field_unchecked(start, width).reverse_low_bits_unchecked(width);
+        self.insert_field_unchecked(start, width, reversed)
+    }
+
+    /// Reverses the order of the bits in the specified range, leaving the other bits unchanged
+    ///
+    /// **Note:** Returns `None` when the `range` is not in the allowed range.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b1100_0101.reverse_bits_in_range(2..6);
+    /// assert_eq!(other, Some(0b1110_0001));
+    ///
+    /// let other: Option<u8> = 0b1100_0101.reverse_bits_in_range(2..10);
+    /// assert_eq!(other, None);
+    /// ```
+    fn reverse_bits_in_range<R: RangeBounds<usize>>(self, range: R) -> Option<Self> {
+        check_range_or_return_none!(range, Self::bit_size());
+        Some(self.reverse_bits_in_range_unchecked(range))
+    }
+
+    /// Reverses the order of the `n` least significant bits and turns off the other bits
+    ///
+    /// **Note:** This function does not check that `n` is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b1010_0011.reverse_low_bits_unchecked(3);
+    /// assert_eq!(other, 0b110);
+    /// ```
+    fn reverse_low_bits_unchecked(self, n: usize) -> Self;
+
+    /// Reverses the order of the `n` least significant bits and turns off the other bits
+    ///
+    /// **Note:** Returns `None` when `n` is greater than `bit_size()`.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b1010_0011.reverse_low_bits(3);
+    /// assert_eq!(other, Some(0b110));
+    ///
+    /// let other: Option<u8> = 0b1010_0011.reverse_low_bits(9);
+    /// assert_eq!(other, None);
+    /// ```
+    fn reverse_low_bits(self, n: usize) -> Option<Self> {
+        if n > Self::bit_size() {
+            return None;
+        }
+        Some(self.reverse_low_bits_unchecked(n))
+    }
+
+    /// Converts the number to its reflected binary Gray code
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b0110.to_gray();
+    /// assert_eq!(other, 0b0101);
+    /// ```
+    fn to_gray(self) -> Self {
+        self ^ self.extract_field_unchecked(1, Self::bit_size() - 1)
+    }
+
+    /// Converts a reflected binary Gray code back to the number it encodes. This is the
+    /// inverse of `to_gray`.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b0101.from_gray();
+    /// assert_eq!(other, 0b0110);
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_gray(self) -> Self {
+        let bits = Self::bit_size();
+        let mut result = self;
+        let mut shift = 1;
+        while shift < bits {
+            result = result ^ result.extract_field_unchecked(shift, bits - shift);
+            shift *= 2;
+        }
+        result
+    }
+
+    /// Returns an iterator over all the numbers made of the `n` least significant bits in
+    /// reflected Gray code order, together with the position of the bit that changed
+    ///
+    /// **Note:** Consecutive numbers differ in exactly one bit, so the state that depends on the
+    /// number can be updated in constant time. When `n` is greater than `bit_size()` all the bits
+    /// are used.
+    ///
+    /// **Possible values:** `GrayCodes`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Vec<(u8, Option<usize>)> = u8::gray_codes(2).collect();
+    /// assert_eq!(other, vec![(0b00, None), (0b01, Some(0)), (0b11, Some(1)), (0b10, Some(0))]);
+    /// ```
+    fn gray_codes(n: usize) -> GrayCodes<Self> {
+        GrayCodes::new(n)
+    }
+
+    /// Shifts the double-width number made of `hi` and `lo` to the left by `n` bits and
+    /// returns the most significant half
+    ///
+    /// **Note:** `n` is taken modulo `bit_size()`, so `funnel_shl(x, x, n)` rotates `x` to the left.
+    /// The standard library may add an associated function with this name, so prefer calling it
+    /// through the trait.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = <u8 as Bitwise>::funnel_shl(0b0000_0011, 0b1010_0000, 3);
+    /// assert_eq!(other, 0b0001_1101);
+    /// ```
+    fn funnel_shl(hi: Self, lo: Self, n: usize) -> Self {
+        let bits = Self::bit_size();
+        let n = n % bits;
+        if n == 0 {
+            return hi;
+        }
+        (hi << n as u32) | lo.extract_field_unchecked(bits - n, n)
+    }
+
+    /// Shifts the double-width number made of `hi` and `lo` to the right by `n` bits and
+    /// returns the least significant half
+    ///
+    /// **Note:** `n` is taken modulo `bit_size()`, so `funnel_shr(x, x, n)` rotates `x` to the right.
+    /// The standard library may add an associated function with this name, so prefer calling it
+    /// through the trait.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = <u8 as Bitwise>::funnel_shr(0b0000_0101, 0b1010_0000, 3);
+    /// assert_eq!(other, 0b1011_0100);
+    /// ```
+    fn funnel_shr(hi: Self, lo: Self, n: usize) -> Self {
+        let bits = Self::bit_size();
+        let n = n % bits;
+        if n == 0 {
+            return lo;
+        }
+        lo.extract_field_unchecked(n, bits - n) | (hi << (bits - n) as u32)
+    }
+
+    /// Rotates the bits in the specified range to the left by `n` bits, leaving the other bits
+    /// unchanged
+    ///
+    /// **Note:** This function does not check that the `range` is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b1110_0111.rotate_range_left_unchecked(2..6, 1);
+    /// assert_eq!(other, 0b1100_1111);
+    /// ```
+    fn rotate_range_left_unchecked<R: RangeBounds<usize>>(self, range: R, n: usize) -> Self {
+        let (start, end) = range_to_bounds(&range, Self::bit_size());
+        if start >= end {
+            return self;
+        }
+        let width = end - start;
+        let n = n % width;
+        if n == 0 {
+            return self;
+        }
+        let field = self.extract_field_unchecked(start, width);
+        let rotated = (field << n as u32) | field.extract_field_unchecked(width - n, n);
+        self.insert_field_unchecked(start, width, rotated)
+    }
+
+    /// Rotates the bits in the specified range to the left by `n` bits, leaving the other bits
+    /// unchanged
+    ///
+    /// **Note:** Returns `None` when the `range` is not in the allowed range.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b1110_0111.rotate_range_left(2..6, 1);
+    /// assert_eq!(other, Some(0b1100_1111));
+    ///
+    /// let other: Option<u8> = 0b1110_0111.rotate_range_left(2..16, 1);
+    /// assert_eq!(other, None);
+    /// ```
+    fn rotate_range_left<R: RangeBounds<usize>>(self, range: R, n: usize) -> Option<Self> {
+        check_range_or_return_none!(range, Self::bit_size());
+        Some(self.rotate_range_left_unchecked(range, n))
+    }
+
+    /// Rotates the bits in the specified range to the right by `n` bits, leaving the other bits
+    /// unchanged
+    ///
+    /// **Note:** This function does not check that the `range` is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b1110_0111.rotate_range_right_unchecked(2..6, 1);
+    /// assert_eq!(other, 0b1111_0011);
+    /// ```
+    fn rotate_range_right_unchecked<R: RangeBounds<usize>>(self, range: R, n: usize) -> Self {
+        let (start, end) = range_to_bounds(&range, Self::bit_size());
+        if start >= end {
+            return self;
+        }
+        let width = end - start;
+        self.rotate_range_left_unchecked(start..end, width - n % width)
+    }
+
+    /// Rotates the bits in the specified range to the right by `n` bits, leaving the other bits
+    /// unchanged
+    ///
+    /// **Note:** Returns `None` when the `range` is not in the allowed range.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b1110_0111.rotate_range_right(2..6, 1);
+    /// assert_eq!(other, Some(0b1111_0011));
+    ///
+    /// let other: Option<u8> = 0b1110_0111.rotate_range_right(2..16, 1);
+    /// assert_eq!(other, None);
+    /// ```
+    fn rotate_range_right<R: RangeBounds<usize>>(self, range: R, n: usize) -> Option<Self> {
+        check_range_or_return_none!(range, Self::bit_size());
+        Some(self.rotate_range_right_unchecked(range, n))
+    }
+
+    /// Interprets the `width` least significant bits as a signed number and extends its sign
+    /// to the remaining bits
+    ///
+    /// **Note:** This function does not check that `width` is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: i16 = 0x0FFF.sign_extend_from_unchecked(12);
+    /// assert_eq!(other, -1);
+    /// ```
+    fn sign_extend_from_unchecked(self, width: usize) -> Self {
+        self.extract_field_signed_unchecked(0, width)
+    }
+
+    /// Interprets the `width` least significant bits as a signed number and extends its sign
+    /// to the remaining bits
+    ///
+    /// **Note:** Returns `None` when `width` is greater than `bit_size()`.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<i32> = 0x0010_0000.sign_extend_from(21);
+    /// assert_eq!(other, Some(-0x0010_0000));
+    ///
+    /// let other: Option<i16> = 0x07FF.sign_extend_from(12);
+    /// assert_eq!(other, Some(0x07FF));
+    ///
+    /// let other: Option<i16> = 0x07FF.sign_extend_from(17);
+    /// assert_eq!(other, None);
+    /// ```
+    fn sign_extend_from(self, width: usize) -> Option<Self> {
+        self.extract_field_signed(0, width)
+    }
+
+    /// Keeps the `width` least significant bits and turns off the remaining bits
+    ///
+    /// **Note:** This function does not check that `width` is within the allowed range.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: i16 = (-1).zero_extend_from_unchecked(12);
+    /// assert_eq!(other, 0x0FFF);
+    /// ```
+    fn zero_extend_from_unchecked(self, width: usize) -> Self {
+        self.extract_field_unchecked(0, width)
+    }
+
+    /// Keeps the `width` least significant bits and turns off the remaining bits
+    ///
+    /// **Note:** Returns `None` when `width` is greater than `bit_size()`.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<i16> = (-1).zero_extend_from(12);
+    /// assert_eq!(other, Some(0x0FFF));
+    ///
+    /// let other: Option<i16> = (-1).zero_extend_from(17);
+    /// assert_eq!(other, None);
+    /// ```
+    fn zero_extend_from(self, width: usize) -> Option<Self> {
+        self.extract_field(0, width)
+    }
+
+    /// Converts the number to another type, keeping its least significant bits and turning
+    /// off the bits that do not exist in the number
+    ///
+    /// **Note:** Works between any two types implementing `Bitwise`, signed or unsigned.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0x1234u16.truncate_to::<u8>();
+    /// assert_eq!(other, 0x34);
+    ///
+    /// let other: u32 = (-1i8).truncate_to::<u32>();
+    /// assert_eq!(other, 0xFF);
+    /// ```
+    fn truncate_to<U: Bitwise>(self) -> U {
+        let bits = U::bit_size();
+        self.iter_ones()
+            .take_while(|&index| index < bits)
+            .fold(U::zero(), |result, index| result.set_bit_unchecked(index))
+    }
+
+    /// Converts the number to another type, keeping its least significant bits and copying
+    /// the most significant bit of the number into the bits that do not exist in the number
+    ///
+    /// **Note:** Works between any two types implementing `Bitwise`, signed or unsigned.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u32 = 0x80u8.sign_extend_to::<u32>();
+    /// assert_eq!(other, 0xFFFF_FF80);
+    ///
+    /// let other: i64 = (-5i16).sign_extend_to::<i64>();
+    /// assert_eq!(other, -5);
+    /// ```
+    fn sign_extend_to<U: Bitwise>(self) -> U {
+        let bits = Self::bit_size();
+        let result = self.truncate_to::<U>();
+        if U::bit_size() > bits && self.get_bit_unchecked(bits - 1) {
+            return result.set_range_unchecked(bits..);
+        }
+        result
+    }
+
+    /// Returns the base 2 logarithm of the number, rounded down
+    ///
+    /// **Note:** Returns `None` when the number is zero or negative.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 20u8.floor_log2();
+    /// assert_eq!(other, Some(4));
+    ///
+    /// let other: Option<usize> = (-20i8).floor_log2();
+    /// assert_eq!(other, None);
+    /// ```
+    fn floor_log2(self) -> Option<usize> {
+        if self <= Self::zero() {
+            return None;
+        }
+        self.highest_set_bit()
+    }
+
+    /// Returns the base 2 logarithm of the number, rounded up
+    ///
+    /// **Note:** Returns `None` when the number is zero or negative.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<usize> = 20u8.ceil_log2();
+    /// assert_eq!(other, Some(5));
+    ///
+    /// let other: Option<usize> = 16i32.ceil_log2();
+    /// assert_eq!(other, Some(4));
+    /// ```
+    fn ceil_log2(self) -> Option<usize> {
+        let floor = self.floor_log2()?;
+        if self.is_power_of_two() {
+            return Some(floor);
+        }
+        Some(floor + 1)
+    }
+
+    /// Returns `true` when the number is a power of two
+    ///
+    /// **Note:** Zero and the negative numbers are not powers of two. The unsigned integers
+    /// have an inherent method with this name and the same behavior.
+    ///
+    /// **Possible values:** `true`, `false`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert!(64i8.is_power_of_two());
+    /// assert!(!i8::MIN.is_power_of_two());
+    /// ```
+    fn is_power_of_two(self) -> bool {
+        self > Self::zero() && self.count_ones_in_range_unchecked(..) == 1
+    }
+
+    /// Returns the smallest power of two greater than or equal to the number
+    ///
+    /// **Note:** Returns `None` when the number is negative or the power of two does not
+    /// fit in the type.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 20u8.next_power_of_two_checked();
+    /// assert_eq!(other, Some(32));
+    ///
+    /// let other: Option<i8> = 65i8.next_power_of_two_checked();
+    /// assert_eq!(other, None);
+    /// ```
+    fn next_power_of_two_checked(self) -> Option<Self> {
+        if self == Self::zero() {
+            return Some(Self::one());
+        }
+        let log2 = self.ceil_log2()?;
+        if log2 >= Self::bit_size() {
+            return None;
+        }
+        let power = Self::zero().set_bit_unchecked(log2);
+        if power < Self::zero() {
+            return None;
+        }
+        Some(power)
+    }
+
+    /// Returns the largest power of two less than or equal to the number
+    ///
+    /// **Note:** Returns `None` when the number is zero or negative.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 20u8.prev_power_of_two();
+    /// assert_eq!(other, Some(16));
+    ///
+    /// let other: Option<u8> = 0u8.prev_power_of_two();
+    /// assert_eq!(other, None);
+    /// ```
+    fn prev_power_of_two(self) -> Option<Self> {
+        self.floor_log2().map(|log2| Self::zero().set_bit_unchecked(log2))
+    }
+
+    /// Keeps only the least significant bit turned on
+    ///
+    /// **Note:** Returns zero when all the bits are turned off. The standard library may add
+    /// an inherent method with this name, so prefer calling it through the trait.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = Bitwise::isolate_lowest_one(0b01011000);
+    /// assert_eq!(other, 0b00001000);
+    /// ```
+    fn isolate_lowest_one(self) -> Self {
+        match self.lowest_set_bit() {
+            Some(index) => Self::zero().set_bit_unchecked(index),
+            None => Self::zero(),
+        }
+    }
+
+    /// Turns off the least significant bit turned on
+    ///
+    /// **Note:** Returns zero when all the bits are turned off.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b01011000.clear_lowest_one();
+    /// assert_eq!(other, 0b01010000);
+    /// ```
+    fn clear_lowest_one(self) -> Self {
+        match self.lowest_set_bit() {
+            Some(index) => self.clear_bit_unchecked(index),
+            None => self,
+        }
+    }
+
+    /// Keeps only the most significant bit turned on
+    ///
+    /// **Note:** Returns zero when all the bits are turned off, and the sign bit alone for
+    /// the negative numbers. The standard library may add an inherent method with this
+    /// name, so prefer calling it through the trait.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = Bitwise::isolate_highest_one(0b01011000);
+    /// assert_eq!(other, 0b01000000);
+    ///
+    /// let other: i8 = Bitwise::isolate_highest_one(-3i8);
+    /// assert_eq!(other, i8::MIN);
+    /// ```
+    fn isolate_highest_one(self) -> Self {
+        match self.highest_set_bit() {
+            Some(index) => Self::zero().set_bit_unchecked(index),
+            None => Self::zero(),
+        }
+    }
+
+    /// Returns a number with only the least significant bit turned off in this number
+    /// turned on
+    ///
+    /// **Note:** Returns zero when all the bits are turned on.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b01010111.lowest_zero_mask();
+    /// assert_eq!(other, 0b00001000);
+    /// ```
+    fn lowest_zero_mask(self) -> Self {
+        match self.lowest_clear_bit() {
+            Some(index) => Self::zero().set_bit_unchecked(index),
+            None => Self::zero(),
+        }
+    }
+
+    /// Returns the set of the bits turned on in the number or in `other`
+    ///
+    /// **Note:** Treats the number as the set of the positions of its bits turned on.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b0110.union(0b0011);
+    /// assert_eq!(other, 0b0111);
+    /// ```
+    fn union(self, other: Self) -> Self {
+        self | other
+    }
+
+    /// Returns the set of the bits turned on in both the number and `other`
+    ///
+    /// **Note:** Treats the number as the set of the positions of its bits turned on.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b0110.intersection(0b0011);
+    /// assert_eq!(other, 0b0010);
+    /// ```
+    fn intersection(self, other: Self) -> Self {
+        self & other
+    }
+
+    /// Returns the set of the bits turned on in the number but not in `other`
+    ///
+    /// **Note:** Treats the number as the set of the positions of its bits turned on.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b0110.difference(0b0011);
+    /// assert_eq!(other, 0b0100);
+    /// ```
+    fn difference(self, other: Self) -> Self {
+        self & !other
+    }
+
+    /// Returns the set of the bits turned on in exactly one of the number and `other`
+    ///
+    /// **Note:** Treats the number as the set of the positions of its bits turned on.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: u8 = 0b0110.symmetric_difference(0b0011);
+    /// assert_eq!(other, 0b0101);
+    /// ```
+    fn symmetric_difference(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    /// Returns `true` when the bit in the index position is turned on
+    ///
+    /// **Note:** Returns `false` when the `index` is not in the allowed range, since the
+    /// set can not hold it.
+    ///
+    /// **Possible values:** `true`, `false`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let workers: u64 = 0b1010;
+    /// assert!(workers.contains(3));
+    /// assert!(!workers.contains(2));
+    /// assert!(!workers.contains(64));
+    /// ```
+    fn contains(self, index: usize) -> bool {
+        self.get_bit(index).unwrap_or(false)
+    }
+
+    /// Adds the index position to the set, turning on its bit
+    ///
+    /// **Note:** Returns `None` when the `index` is not in the allowed range.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b0010.insert(3);
+    /// assert_eq!(other, Some(0b1010));
+    ///
+    /// let other: Option<u8> = 0b0010.insert(8);
+    /// assert_eq!(other, None);
+    /// ```
+    fn insert(self, index: usize) -> Option<Self> {
+        self.set_bit(index)
+    }
+
+    /// Removes the index position from the set, turning off its bit
+    ///
+    /// **Note:** Returns `None` when the `index` is not in the allowed range.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b1010.remove(3);
+    /// assert_eq!(other, Some(0b0010));
+    /// ```
+    fn remove(self, index: usize) -> Option<Self> {
+        self.clear_bit(index)
+    }
+
+    /// Returns `true` when every bit turned on in the number is turned on in `other`
+    ///
+    /// **Note:** Treats the number as the set of the positions of its bits turned on.
+    ///
+    /// **Possible values:** `true`, `false`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert!(0b0010u8.is_subset_of(0b0110));
+    /// assert!(!0b0011u8.is_subset_of(0b0110));
+    /// ```
+    fn is_subset_of(self, other: Self) -> bool {
+        self & !other == Self::zero()
+    }
+
+    /// Returns `true` when every bit turned on in `other` is turned on in the number
+    ///
+    /// **Note:** Treats the number as the set of the positions of its bits turned on.
+    ///
+    /// **Possible values:** `true`, `false`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert!(0b0110u8.is_superset_of(0b0010));
+    /// assert!(!0b0110u8.is_superset_of(0b0011));
+    /// ```
+    fn is_superset_of(self, other: Self) -> bool {
+        other.is_subset_of(self)
+    }
+
+    /// Returns `true` when no bit is turned on in both the number and `other`
+    ///
+    /// **Note:** Treats the number as the set of the positions of its bits turned on.
+    ///
+    /// **Possible values:** `true`, `false`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert!(0b0110u8.is_disjoint(0b1001));
+    /// assert!(!0b0110u8.is_disjoint(0b0011));
+    /// ```
+    fn is_disjoint(self, other: Self) -> bool {
+        self & other == Self::zero()
+    }
+
+    /// Returns the number of elements of the set, that is, the number of bits turned on
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert_eq!(0b0110u8.cardinality(), 2);
+    /// assert_eq!((-1i64).cardinality(), 64);
+    /// ```
+    fn cardinality(self) -> usize {
+        self.count_ones_in_range_unchecked(..)
+    }
+
+    /// Builds the set that holds the given index positions, turning on their bits
+    ///
+    /// **Note:** Returns `None` when any index is not in the allowed range. The integer
+    /// types are foreign to this crate, so this takes the place of a `FromIterator<usize>`
+    /// implementation.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u64> = u64::from_indices(vec![1, 3, 5]);
+    /// assert_eq!(other, Some(0b101010));
+    ///
+    /// let other: Option<u8> = u8::from_indices(0..9);
+    /// assert_eq!(other, None);
+    /// ```
+    fn from_indices<I: IntoIterator<Item = usize>>(indices: I) -> Option<Self> {
+        indices.into_iter().try_fold(Self::zero(), |set, index| set.insert(index))
+    }
+
+    /// Compares the number with `other` as sets of bit positions in the given `order`
+    ///
+    /// **Note:** See [`SetOrder`] for the available orders.
+    ///
+    /// **Possible values:** `Less`, `Equal`, `Greater`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cmp::Ordering;
+    /// use xbitwise::order::SetOrder;
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert_eq!(0b011u8.cmp_in_order(0b100, SetOrder::Colex), Ordering::Less);
+    /// assert_eq!(0b011u8.cmp_in_order(0b100, SetOrder::Lex), Ordering::Less);
+    /// assert_eq!(0b011u8.cmp_in_order(0b100, SetOrder::Popcount), Ordering::Greater);
+    ///
+    /// let mut sets: Vec<u8> = vec![0b100, 0b010, 0b011, 0b001];
+    /// sets.sort_by(|a, b| a.cmp_in_order(*b, SetOrder::Lex));
+    /// assert_eq!(sets, [0b001, 0b011, 0b010, 0b100]);
+    /// ```
+    fn cmp_in_order(self, other: Self, order: SetOrder) -> Ordering {
+        order::cmp_in_order(self, other, order)
+    }
+
+    /// Returns the set of bit positions that comes right after the number in the given `order`
+    ///
+    /// **Note:** Returns `None` when the number is the last set of the order.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::order::SetOrder;
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b0011.next_in_order(SetOrder::Lex);
+    /// assert_eq!(other, Some(0b0111));
+    ///
+    /// let other: Option<u8> = 0b1100_0000.next_in_order(SetOrder::Popcount);
+    /// assert_eq!(other, Some(0b0000_0111));
+    /// ```
+    fn next_in_order(self, order: SetOrder) -> Option<Self> {
+        order::next_in_order(self, order)
+    }
+
+    /// Returns the set of bit positions that comes right before the number in the given `order`
+    ///
+    /// **Note:** Returns `None` when the number is the first set of the order.
+    ///
+    /// **Possible values:** `None`, `Some(integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::order::SetOrder;
+    /// use xbitwise::Bitwise;
+    ///
+    /// let other: Option<u8> = 0b0010.prev_in_order(SetOrder::Lex);
+    /// assert_eq!(other, Some(0b1000_0001));
+    ///
+    /// let other: Option<u8> = 0.prev_in_order(SetOrder::Colex);
+    /// assert_eq!(other, None);
+    /// ```
+    fn prev_in_order(self, order: SetOrder) -> Option<Self> {
+        order::prev_in_order(self, order)
+    }
+
+    /// Return a number with all bits off (an integer of value zero).
+    /// 
+    /// **Possible values:** `0`
+    /// 
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert_eq!(i8::zero(), 0i8);
+    /// ```
+    fn zero() -> Self;
+
+    /// Returns a number with the least significant bit on (an integer with value one).
+    ///
+    /// **Possible values:** `1`
+    /// 
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// assert_eq!(i8::one(), 1i8);
+    /// ```
+    fn one() -> Self;
+
+    /// Returns the number of bits of the number
+    ///
+    /// **Possible values:** `integer`
+    /// 
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::Bitwise;
+    ///
+    /// let expected: usize = 8;
+    /// assert_eq!(i8::bit_size(), 8usize);
+    /// ```
+    fn bit_size() -> usize;
+}
+
+/// Converts any range of bit indexes into a half-open `(start, end)` pair,
+/// using `max_bits` as the end of an unbounded range.
+pub(crate) fn range_to_bounds<R: RangeBounds<usize>>(range: &R, max_bits: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Included(val) => *val,
+        Excluded(val) => val.saturating_add(1),
+        Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Included(val) => val.saturating_add(1),
+        Excluded(val) => *val,
+        Unbounded => max_bits,
+    };
+    (start, end)
+}
+
+macro_rules! impl_bitwise {
+    ($($max_bits:expr => $t:ident),*) => {$(
+
+        impl Bitwise for $t {
+
+            fn get_bit_unchecked(self, index: usize) -> bool {
+                let mask = (Self::one() << index);
+                (self & mask) == mask
+            }
 
-            fn update_bit(self, index: usize, new_value: bool) -> Option<Self> {
-                check_bit_index_or_return_none!(index, $max_bits);
-                Some(self.update_bit_unchecked(index, new_value))
+            fn set_bit_unchecked(self, index: usize) -> Self {
+                self | (Self::one() << index)
             }
 
-            fn clear_bit_unchecked(self, index: usize) -> Self {
-                self & !(Self::one() << index)
+            fn get_range_mask_unchecked<R: RangeBounds<usize>>(range: R) -> Self {
+                let (start, end) = range_to_bounds(&range, $max_bits);
+                let width = end.saturating_sub(start);
+                if width == 0 {
+                    return 0;
+                }
+                let low = if width >= $max_bits { !0 } else { !((!0 as Self) << width) };
+                low << start
             }
 
-            fn clear_bit(self, index: usize) -> Option<Self> {
-                check_bit_index_or_return_none!(index, $max_bits);
-                Some(self.clear_bit_unchecked(index))
+            fn count_ones_in_range_unchecked<R: RangeBounds<usize>>(self, range: R) -> usize {
+                (self & Self::get_range_mask_unchecked(range)).count_ones() as usize
             }
 
-            fn clear(self) -> Self {
-                self & 0
+            fn extract_field_unchecked(self, offset: usize, width: usize) -> Self {
+                if width == 0 {
+                    return 0;
+                }
+                (self >> offset) & Self::get_range_mask_unchecked(..width)
+            }
+
+            fn clear_bit_unchecked(self, index: usize) -> Self {
+                self & !(Self::one() << index)
             }
 
             fn flip_bit_unchecked(self, index: usize) -> Self {
                 self ^ (Self::one() << index)
             }
 
-            fn flip_bit(self, index: usize) -> Option<Self> {
-                check_bit_index_or_return_none!(index, $max_bits);
-                Some(self.flip_bit_unchecked(index))
+            fn count_trailing_zeros(self) -> usize {
+                self.trailing_zeros() as usize
+            }
+
+            fn count_leading_zeros(self) -> usize {
+                self.leading_zeros() as usize
+            }
+
+            fn deposit_bits(self, mask: Self) -> Self {
+                #[cfg(all(feature = "bmi2", target_arch = "x86_64"))]
+                {
+                    if let Some(result) = pdep::Hardware::deposit_bits(self, mask) {
+                        return result;
+                    }
+                }
+                pdep::deposit_bits(self, mask)
             }
 
-            fn flip(self) -> Self {
-                !self
+            fn extract_bits(self, mask: Self) -> Self {
+                #[cfg(all(feature = "bmi2", target_arch = "x86_64"))]
+                {
+                    if let Some(result) = pdep::Hardware::extract_bits(self, mask) {
+                        return result;
+                    }
+                }
+                pdep::extract_bits(self, mask)
             }
 
-            fn parity(self) -> bool {
-                ((self.count_ones() as Self) & Self::one()) == Self::one()
+            fn reverse_low_bits_unchecked(self, n: usize) -> Self {
+                if n == 0 {
+                    return 0;
+                }
+                self.reverse_bits().extract_field_unchecked($max_bits - n, n)
             }
 
-            fn hamming_distance(self, other: Self) -> usize {
-                (self ^ other).count_ones() as usize
+            fn is_power_of_two(self) -> bool {
+                self > Self::zero() && self & (self - 1) == 0
+            }
+
+            fn isolate_lowest_one(self) -> Self {
+                self & self.wrapping_neg()
+            }
+
+            fn clear_lowest_one(self) -> Self {
+                self & self.wrapping_sub(1)
+            }
+
+            fn lowest_zero_mask(self) -> Self {
+                !self & self.wrapping_add(1)
             }
 
             fn zero() -> Self { 0 }
@@ -604,6 +2601,7 @@ impl_bitwise!(U64_BITS => u64);
 impl_bitwise!(U128_BITS => u128);
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use crate::Bitwise;
 
@@ -661,6 +2659,180 @@ mod tests {
         assert_eq!(other, Some(0b11100100));
     }
 
+    #[allow(clippy::reversed_empty_ranges)]
+    #[test]
+    fn set_range_full_width() {
+        macro_rules! check {
+            ($($t:ident),*) => {$(
+                let bits = $t::bit_size();
+                assert_eq!($t::zero().set_range(..), Some(!0));
+                assert_eq!($t::zero().set_range(0..bits), Some(!0));
+                assert_eq!($t::zero().set_range(0..=bits - 1), Some(!0));
+                assert_eq!($t::zero().set_range(bits - 1..), Some($t::one() << (bits - 1)));
+                assert_eq!($t::zero().set_range(bits..), Some(0));
+                assert_eq!($t::zero().set_range(3..3), Some(0));
+                assert_eq!($t::zero().set_range(..=bits), None);
+                assert_eq!($t::zero().set_range(bits + 1..), None);
+                assert_eq!($t::zero().set_range(4..3), None);
+            )*};
+        }
+        check!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+    }
+
+    #[test]
+    fn clear_range_unchecked() {
+        let other: u8 = 0b11111111.clear_range_unchecked(2..5);
+        assert_eq!(other, 0b11100011);
+        let other: i8 = (-1i8).clear_range_unchecked(..);
+        assert_eq!(other, 0);
+    }
+
+    #[test]
+    fn clear_range() {
+        let other: Option<u8> = 0b11111111.clear_range(2..=4);
+        assert_eq!(other, Some(0b11100011));
+        let other: Option<u8> = 0b11111111.clear_range(..=8);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn flip_range_unchecked() {
+        let other: u8 = 0b00001111.flip_range_unchecked(2..6);
+        assert_eq!(other, 0b00110011);
+    }
+
+    #[allow(clippy::reversed_empty_ranges)]
+    #[test]
+    fn flip_range() {
+        let other: Option<u8> = 0b00001111.flip_range(2..6);
+        assert_eq!(other, Some(0b00110011));
+        let other: Option<i16> = 0.flip_range(..);
+        assert_eq!(other, Some(-1));
+        let other: Option<u8> = 0b00001111.flip_range(6..2);
+        assert_eq!(other, None);
+    }
+
+    #[allow(overflowing_literals)]
+    #[test]
+    fn get_range_mask_unchecked() {
+        assert_eq!(u8::get_range_mask_unchecked(2..5), 0b00011100);
+        assert_eq!(i8::get_range_mask_unchecked(1..), 0b11111110);
+        assert_eq!(u128::get_range_mask_unchecked(..), u128::MAX);
+    }
+
+    #[test]
+    fn get_range_mask() {
+        assert_eq!(u8::get_range_mask(2..5), Some(0b00011100));
+        assert_eq!(u64::get_range_mask(63..64), Some(1 << 63));
+        assert_eq!(u8::get_range_mask(2..9), None);
+    }
+
+    #[test]
+    fn count_ones_in_range_unchecked() {
+        let other: usize = 0b10110110u8.count_ones_in_range_unchecked(2..6);
+        assert_eq!(other, 3);
+    }
+
+    #[test]
+    fn count_ones_in_range() {
+        let other: Option<usize> = 0b10110110u8.count_ones_in_range(..);
+        assert_eq!(other, Some(5));
+        let other: Option<usize> = (-1i128).count_ones_in_range(64..);
+        assert_eq!(other, Some(64));
+        let other: Option<usize> = 0b10110110u8.count_ones_in_range(..=8);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn update_range_unchecked() {
+        let other: u8 = 0b00000000.update_range_unchecked(1..3, true);
+        assert_eq!(other, 0b00000110);
+        let other: u8 = 0b11111111.update_range_unchecked(1..3, false);
+        assert_eq!(other, 0b11111001);
+    }
+
+    #[test]
+    fn update_range() {
+        let other: Option<u8> = 0b00000000.update_range(1..3, true);
+        assert_eq!(other, Some(0b00000110));
+        let other: Option<u8> = 0b00000000.update_range(1..10, true);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn extract_field_unchecked() {
+        let other: u16 = 0b1011_0110_0000.extract_field_unchecked(5, 4);
+        assert_eq!(other, 0b1011);
+        let other: i8 = (-128i8).extract_field_unchecked(4, 4);
+        assert_eq!(other, 0b1000);
+    }
+
+    #[test]
+    fn extract_field() {
+        let other: Option<u16> = 0b1011_0110_0000.extract_field(5, 4);
+        assert_eq!(other, Some(0b1011));
+        let other: Option<u16> = 0b1011_0110_0000.extract_field(5, 0);
+        assert_eq!(other, Some(0));
+        let other: Option<u16> = 0b1011_0110_0000.extract_field(14, 4);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn extract_field_signed_unchecked() {
+        let other: i16 = 0b1011_0110_0000.extract_field_signed_unchecked(5, 4);
+        assert_eq!(other, -5);
+        let other: i16 = 0b0110_0110_0000.extract_field_signed_unchecked(5, 4);
+        assert_eq!(other, 3);
+    }
+
+    #[test]
+    fn extract_field_signed() {
+        let other: Option<i16> = 0b1011_0110_0000.extract_field_signed(5, 4);
+        assert_eq!(other, Some(-5));
+        let other: Option<u8> = 0b0111_0000.extract_field_signed(4, 3);
+        assert_eq!(other, Some(0b1111_1111));
+        let other: Option<i32> = (-7i32).extract_field_signed(0, 32);
+        assert_eq!(other, Some(-7));
+        let other: Option<i16> = 0b1011_0110_0000.extract_field_signed(5, 12);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn insert_field_unchecked() {
+        let other: u16 = 0b1111_1111_1111.insert_field_unchecked(4, 4, 0b0101);
+        assert_eq!(other, 0b1111_0101_1111);
+    }
+
+    #[test]
+    fn insert_field() {
+        let other: Option<u16> = 0b1111_1111_1111.insert_field(4, 4, 0b1_0101);
+        assert_eq!(other, Some(0b1111_0101_1111));
+        let other: Option<i8> = 0.insert_field(4, 4, -1);
+        assert_eq!(other, Some(-16));
+        let other: Option<u64> = 0.insert_field(0, 64, u64::MAX);
+        assert_eq!(other, Some(u64::MAX));
+        let other: Option<u16> = 0.insert_field(12, 5, 1);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn field_round_trip() {
+        macro_rules! check {
+            ($($t:ident),*) => {$(
+                let bits = $t::bit_size();
+                for offset in 0..=bits {
+                    for width in 0..=bits - offset {
+                        let value = (!$t::zero()).extract_field_unchecked(0, width);
+                        let word = $t::zero().insert_field(offset, width, value).unwrap();
+                        assert_eq!(word.count_ones_in_range(..), Some(width));
+                        assert_eq!(word.extract_field(offset, width), Some(value));
+                    }
+                }
+            )*};
+        }
+        check!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+    }
+
     #[allow(overflowing_literals)]
     #[test]
     fn set() {
@@ -750,6 +2922,477 @@ mod tests {
         assert_eq!(other, 3);
     }
 
+    #[test]
+    fn count_trailing_zeros() {
+        assert_eq!(0b01011000u8.count_trailing_zeros(), 3);
+        assert_eq!(0u8.count_trailing_zeros(), 8);
+        assert_eq!(i128::MIN.count_trailing_zeros(), 127);
+    }
+
+    #[test]
+    fn count_leading_zeros() {
+        assert_eq!(0b01011000u8.count_leading_zeros(), 1);
+        assert_eq!(0i16.count_leading_zeros(), 16);
+        assert_eq!((-1i64).count_leading_zeros(), 0);
+    }
+
+    #[test]
+    fn next_same_popcount() {
+        let other: Option<u8> = 0b00101100.next_same_popcount();
+        assert_eq!(other, Some(0b00110001));
+        let other: Option<u8> = 0b11100000.next_same_popcount();
+        assert_eq!(other, None);
+        let other: Option<i8> = 0b01000000.next_same_popcount();
+        assert_eq!(other, Some(i8::MIN));
+        let other: Option<i8> = i8::MIN.next_same_popcount();
+        assert_eq!(other, None);
+        let other: Option<u32> = 0.next_same_popcount();
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn lowest_set_bit() {
+        assert_eq!(0b01011000u8.lowest_set_bit(), Some(3));
+        assert_eq!(i64::MIN.lowest_set_bit(), Some(63));
+        assert_eq!(0u8.lowest_set_bit(), None);
+    }
+
+    #[test]
+    fn highest_set_bit() {
+        assert_eq!(0b01011000u8.highest_set_bit(), Some(6));
+        assert_eq!((-1i16).highest_set_bit(), Some(15));
+        assert_eq!(0u128.highest_set_bit(), None);
+    }
+
+    #[test]
+    fn next_set_bit() {
+        assert_eq!(0b01011000u8.next_set_bit(0), Some(3));
+        assert_eq!(0b01011000u8.next_set_bit(4), Some(4));
+        assert_eq!(0b01011000u8.next_set_bit(5), Some(6));
+        assert_eq!(0b01011000u8.next_set_bit(7), None);
+        assert_eq!((-1i8).next_set_bit(8), None);
+    }
+
+    #[test]
+    fn prev_set_bit() {
+        assert_eq!(0b01011000u8.prev_set_bit(5), Some(4));
+        assert_eq!(0b01011000u8.prev_set_bit(3), Some(3));
+        assert_eq!(0b01011000u8.prev_set_bit(2), None);
+        assert_eq!(i32::MIN.prev_set_bit(usize::MAX), Some(31));
+    }
+
+    #[test]
+    fn lowest_clear_bit() {
+        assert_eq!(0b00000111u8.lowest_clear_bit(), Some(3));
+        assert_eq!(u8::MAX.lowest_clear_bit(), None);
+    }
+
+    #[test]
+    fn highest_clear_bit() {
+        assert_eq!(0b10011111u8.highest_clear_bit(), Some(6));
+        assert_eq!(0i32.highest_clear_bit(), Some(31));
+        assert_eq!((-1i32).highest_clear_bit(), None);
+    }
+
+    #[test]
+    fn next_clear_bit() {
+        assert_eq!(0b11100111u8.next_clear_bit(1), Some(3));
+        assert_eq!(0b11100111u8.next_clear_bit(4), Some(4));
+        assert_eq!(0b11100111u8.next_clear_bit(5), None);
+    }
+
+    #[test]
+    fn prev_clear_bit() {
+        assert_eq!(0b11100111u8.prev_clear_bit(7), Some(4));
+        assert_eq!(0b11100111u8.prev_clear_bit(2), None);
+        assert_eq!(0u16.prev_clear_bit(20), Some(15));
+    }
+
+    #[test]
+    fn select() {
+        assert_eq!(0b01011000u8.select(0), Some(3));
+        assert_eq!(0b01011000u8.select(2), Some(6));
+        assert_eq!(0b01011000u8.select(3), None);
+        assert_eq!((-1i128).select(127), Some(127));
+    }
+
+    #[test]
+    fn scan_brute_force() {
+        for value in 0..=u8::MAX {
+            for from in 0..10 {
+                let next = (from..8).find(|&i| value.get_bit_unchecked(i));
+                let prev = (0..=from.min(7)).rev().find(|&i| value.get_bit_unchecked(i));
+                assert_eq!(value.next_set_bit(from), next);
+                assert_eq!(value.prev_set_bit(from), prev);
+                assert_eq!((value as i8).next_set_bit(from), next);
+                assert_eq!((value as i8).prev_set_bit(from), prev);
+                assert_eq!(value.select(from), (0..8).filter(|&i| value.get_bit_unchecked(i)).nth(from));
+            }
+        }
+    }
+
+    #[test]
+    fn reverse_bits_in_range_unchecked() {
+        let other: u8 = 0b1100_0101.reverse_bits_in_range_unchecked(2..6);
+        assert_eq!(other, 0b1110_0001);
+        let other: i8 = 1.reverse_bits_in_range_unchecked(..);
+        assert_eq!(other, i8::MIN);
+    }
+
+    #[test]
+    fn reverse_bits_in_range() {
+        let other: Option<u8> = 0b1100_0101.reverse_bits_in_range(2..6);
+        assert_eq!(other, Some(0b1110_0001));
+        let other: Option<u16> = 0b1101.reverse_bits_in_range(3..3);
+        assert_eq!(other, Some(0b1101));
+        let other: Option<i64> = (-2i64).reverse_bits_in_range(..=62);
+        assert_eq!(other, Some(!(1 << 62)));
+        let other: Option<u8> = 0b1100_0101.reverse_bits_in_range(2..10);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn reverse_low_bits_unchecked() {
+        let other: u8 = 0b1010_0011.reverse_low_bits_unchecked(3);
+        assert_eq!(other, 0b110);
+        let other: u32 = 0b1.reverse_low_bits_unchecked(0);
+        assert_eq!(other, 0);
+    }
+
+    #[test]
+    fn reverse_low_bits() {
+        let other: Option<u8> = 0b1010_0011.reverse_low_bits(3);
+        assert_eq!(other, Some(0b110));
+        let other: Option<i16> = 1.reverse_low_bits(16);
+        assert_eq!(other, Some(i16::MIN));
+        let other: Option<u8> = 0b1010_0011.reverse_low_bits(9);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn to_gray() {
+        let other: u8 = 0b0110.to_gray();
+        assert_eq!(other, 0b0101);
+        let other: i8 = i8::MIN.to_gray();
+        assert_eq!(other, -64);
+        let other: u128 = u128::MAX.to_gray();
+        assert_eq!(other, 1 << 127);
+    }
+
+    #[test]
+    fn from_gray() {
+        let other: u8 = 0b0101.from_gray();
+        assert_eq!(other, 0b0110);
+        for value in 0..=u8::MAX {
+            assert_eq!(value.to_gray().from_gray(), value);
+            assert_eq!((value as i8).to_gray().from_gray(), value as i8);
+            assert_eq!(value.to_gray().hamming_distance((value.wrapping_add(1)).to_gray()), 1);
+        }
+        let other: i64 = (-12345i64).to_gray().from_gray();
+        assert_eq!(other, -12345);
+    }
+
+    #[test]
+    fn funnel_shl() {
+        assert_eq!(<u8 as Bitwise>::funnel_shl(0b0000_0011, 0b1010_0000, 3), 0b0001_1101);
+        assert_eq!(<u8 as Bitwise>::funnel_shl(0b0000_0011, 0b1010_0000, 0), 0b0000_0011);
+        assert_eq!(i8::funnel_shl(0, -1, 1), 1);
+        assert_eq!(<u32 as Bitwise>::funnel_shl(0x1234_5678, 0x1234_5678, 36), 0x1234_5678u32.rotate_left(4));
+    }
+
+    #[test]
+    fn funnel_shr() {
+        assert_eq!(<u8 as Bitwise>::funnel_shr(0b0000_0101, 0b1010_0000, 3), 0b1011_0100);
+        assert_eq!(<u8 as Bitwise>::funnel_shr(0b0000_0101, 0b1010_0000, 8), 0b1010_0000);
+        assert_eq!(i16::funnel_shr(0, -1, 15), 1);
+        assert_eq!(<u64 as Bitwise>::funnel_shr(0xDEAD, 0xDEAD, 12), 0xDEADu64.rotate_right(12));
+    }
+
+    #[test]
+    fn rotate_range_left_unchecked() {
+        let other: u8 = 0b1110_0111.rotate_range_left_unchecked(2..6, 1);
+        assert_eq!(other, 0b1100_1111);
+        let other: i32 = (-2i32).rotate_range_left_unchecked(.., 1);
+        assert_eq!(other, (-2i32).rotate_left(1));
+    }
+
+    #[test]
+    fn rotate_range_left() {
+        let other: Option<u8> = 0b1110_0111.rotate_range_left(2..6, 1);
+        assert_eq!(other, Some(0b1100_1111));
+        let other: Option<u8> = 0b1110_0111.rotate_range_left(2..6, 4);
+        assert_eq!(other, Some(0b1110_0111));
+        let other: Option<u8> = 0b1110_0111.rotate_range_left(3..3, 4);
+        assert_eq!(other, Some(0b1110_0111));
+        let other: Option<u8> = 0b1110_0111.rotate_range_left(2..16, 1);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn rotate_range_right_unchecked() {
+        let other: u8 = 0b1110_0111.rotate_range_right_unchecked(2..6, 1);
+        assert_eq!(other, 0b1111_0011);
+        let other: u128 = 1u128.rotate_range_right_unchecked(.., 1);
+        assert_eq!(other, 1 << 127);
+    }
+
+    #[test]
+    fn rotate_range_right() {
+        let other: Option<u8> = 0b1110_0111.rotate_range_right(2..6, 1);
+        assert_eq!(other, Some(0b1111_0011));
+        for value in 0..=u8::MAX {
+            for n in 0..10 {
+                let left = value.rotate_range_left(1..=6, n).unwrap();
+                assert_eq!(left.rotate_range_right(1..=6, n), Some(value));
+                assert_eq!(left & 0b1000_0001, value & 0b1000_0001);
+                assert_eq!(value.rotate_range_left(.., n), Some(value.rotate_left(n as u32)));
+            }
+        }
+        let other: Option<u8> = 0b1110_0111.rotate_range_right(2..16, 1);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn sign_extend_from_unchecked() {
+        let other: i16 = 0x0FFF.sign_extend_from_unchecked(12);
+        assert_eq!(other, -1);
+        let other: u16 = 0x0800.sign_extend_from_unchecked(12);
+        assert_eq!(other, 0xF800);
+    }
+
+    #[test]
+    fn sign_extend_from() {
+        let other: Option<i32> = 0x0010_0000.sign_extend_from(21);
+        assert_eq!(other, Some(-0x0010_0000));
+        let other: Option<i16> = 0x07FF.sign_extend_from(12);
+        assert_eq!(other, Some(0x07FF));
+        let other: Option<i16> = 0x7FFF.sign_extend_from(0);
+        assert_eq!(other, Some(0));
+        let other: Option<i8> = (-3i8).sign_extend_from(8);
+        assert_eq!(other, Some(-3));
+        let other: Option<i16> = 0x07FF.sign_extend_from(17);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn zero_extend_from_unchecked() {
+        let other: i16 = (-1).zero_extend_from_unchecked(12);
+        assert_eq!(other, 0x0FFF);
+    }
+
+    #[test]
+    fn zero_extend_from() {
+        let other: Option<i16> = (-1).zero_extend_from(12);
+        assert_eq!(other, Some(0x0FFF));
+        let other: Option<u64> = u64::MAX.zero_extend_from(64);
+        assert_eq!(other, Some(u64::MAX));
+        let other: Option<i16> = (-1).zero_extend_from(17);
+        assert_eq!(other, None);
+    }
+
+    #[test]
+    fn truncate_to() {
+        assert_eq!(0x1234u16.truncate_to::<u8>(), 0x34);
+        assert_eq!((-1i8).truncate_to::<u32>(), 0xFF);
+        assert_eq!((-1i128).truncate_to::<i8>(), -1);
+        assert_eq!(0x80u8.truncate_to::<i8>(), i8::MIN);
+        assert_eq!(u64::MAX.truncate_to::<i128>(), u64::MAX as i128);
+    }
+
+    #[test]
+    fn sign_extend_to() {
+        assert_eq!(0x80u8.sign_extend_to::<u32>(), 0xFFFF_FF80);
+        assert_eq!((-5i16).sign_extend_to::<i64>(), -5);
+        assert_eq!((-5i16).sign_extend_to::<u8>(), 0xFB);
+        assert_eq!(0x7Fi8.sign_extend_to::<u128>(), 0x7F);
+        for value in i8::MIN..=i8::MAX {
+            assert_eq!(value.sign_extend_to::<i32>(), value as i32);
+            assert_eq!(value.sign_extend_to::<u16>(), value as u16);
+            assert_eq!(value.truncate_to::<u16>(), value as u8 as u16);
+            assert_eq!((value as i32).truncate_to::<i8>(), value);
+        }
+    }
+
+    #[test]
+    fn floor_log2() {
+        assert_eq!(20u8.floor_log2(), Some(4));
+        assert_eq!(1i32.floor_log2(), Some(0));
+        assert_eq!(u128::MAX.floor_log2(), Some(127));
+        assert_eq!(i64::MAX.floor_log2(), Some(62));
+        assert_eq!(0u16.floor_log2(), None);
+        assert_eq!((-20i8).floor_log2(), None);
+    }
+
+    #[test]
+    fn ceil_log2() {
+        assert_eq!(20u8.ceil_log2(), Some(5));
+        assert_eq!(16i32.ceil_log2(), Some(4));
+        assert_eq!(1u64.ceil_log2(), Some(0));
+        assert_eq!(u8::MAX.ceil_log2(), Some(8));
+        assert_eq!(0u8.ceil_log2(), None);
+        assert_eq!(i16::MIN.ceil_log2(), None);
+    }
+
+    #[test]
+    fn is_power_of_two() {
+        for value in i8::MIN..=i8::MAX {
+            let expected = value > 0 && (value as u8).count_ones() == 1;
+            assert_eq!(Bitwise::is_power_of_two(value), expected);
+        }
+        for value in 0..=u8::MAX {
+            assert_eq!(Bitwise::is_power_of_two(value), value.is_power_of_two());
+        }
+    }
+
+    #[test]
+    fn next_power_of_two_checked() {
+        assert_eq!(20u8.next_power_of_two_checked(), Some(32));
+        assert_eq!(0u8.next_power_of_two_checked(), Some(1));
+        assert_eq!(128u8.next_power_of_two_checked(), Some(128));
+        assert_eq!(129u8.next_power_of_two_checked(), None);
+        assert_eq!(64i8.next_power_of_two_checked(), Some(64));
+        assert_eq!(65i8.next_power_of_two_checked(), None);
+        assert_eq!((-1i32).next_power_of_two_checked(), None);
+        for value in 0..=u16::MAX {
+            assert_eq!(value.next_power_of_two_checked(), value.checked_next_power_of_two());
+        }
+    }
+
+    #[test]
+    fn prev_power_of_two() {
+        assert_eq!(20u8.prev_power_of_two(), Some(16));
+        assert_eq!(16i16.prev_power_of_two(), Some(16));
+        assert_eq!(u128::MAX.prev_power_of_two(), Some(1 << 127));
+        assert_eq!(0u8.prev_power_of_two(), None);
+        assert_eq!((-16i16).prev_power_of_two(), None);
+    }
+
+    #[test]
+    fn isolate_lowest_one() {
+        assert_eq!(Bitwise::isolate_lowest_one(0b01011000u8), 0b00001000);
+        assert_eq!(Bitwise::isolate_lowest_one(i8::MIN), i8::MIN);
+        assert_eq!(Bitwise::isolate_lowest_one(-4i32), 4);
+        assert_eq!(Bitwise::isolate_lowest_one(0u64), 0);
+    }
+
+    #[test]
+    fn clear_lowest_one() {
+        assert_eq!(0b01011000u8.clear_lowest_one(), 0b01010000);
+        assert_eq!(i8::MIN.clear_lowest_one(), 0);
+        assert_eq!((-1i16).clear_lowest_one(), -2);
+        assert_eq!(0u128.clear_lowest_one(), 0);
+    }
+
+    #[test]
+    fn isolate_highest_one() {
+        assert_eq!(Bitwise::isolate_highest_one(0b01011000u8), 0b01000000);
+        assert_eq!(Bitwise::isolate_highest_one(-3i8), i8::MIN);
+        assert_eq!(Bitwise::isolate_highest_one(u128::MAX), 1 << 127);
+        assert_eq!(Bitwise::isolate_highest_one(0i32), 0);
+    }
+
+    #[test]
+    fn lowest_zero_mask() {
+        assert_eq!(0b01010111u8.lowest_zero_mask(), 0b00001000);
+        assert_eq!(u8::MAX.lowest_zero_mask(), 0);
+        assert_eq!((-1i64).lowest_zero_mask(), 0);
+        assert_eq!(i8::MAX.lowest_zero_mask(), i8::MIN);
+        assert_eq!(0u32.lowest_zero_mask(), 1);
+    }
+
+    #[test]
+    fn union() {
+        assert_eq!(0b0110u8.union(0b0011), 0b0111);
+        assert_eq!(i8::MIN.union(1), -127);
+    }
+
+    #[test]
+    fn intersection() {
+        assert_eq!(0b0110u8.intersection(0b0011), 0b0010);
+        assert_eq!((-1i32).intersection(0x70), 0x70);
+    }
+
+    #[test]
+    fn difference() {
+        assert_eq!(0b0110u8.difference(0b0011), 0b0100);
+        assert_eq!((-1i16).difference(-2), 1);
+    }
+
+    #[test]
+    fn symmetric_difference() {
+        assert_eq!(0b0110u8.symmetric_difference(0b0011), 0b0101);
+        assert_eq!(u128::MAX.symmetric_difference(1), u128::MAX - 1);
+    }
+
+    #[test]
+    fn contains() {
+        assert!(0b1010u64.contains(3));
+        assert!(!0b1010u64.contains(2));
+        assert!(!u64::MAX.contains(64));
+        assert!((-1i8).contains(7));
+    }
+
+    #[test]
+    fn insert() {
+        assert_eq!(0b0010u8.insert(3), Some(0b1010));
+        assert_eq!(0b0010u8.insert(1), Some(0b0010));
+        assert_eq!(0i8.insert(7), Some(i8::MIN));
+        assert_eq!(0u8.insert(8), None);
+    }
+
+    #[test]
+    fn remove() {
+        assert_eq!(0b1010u8.remove(3), Some(0b0010));
+        assert_eq!(0b1010u8.remove(2), Some(0b1010));
+        assert_eq!((-1i16).remove(15), Some(i16::MAX));
+        assert_eq!(0u16.remove(16), None);
+    }
+
+    #[test]
+    fn is_subset_of() {
+        for a in 0..=15u8 {
+            for b in 0..=15u8 {
+                let expected = (0..4).all(|index| !a.contains(index) || b.contains(index));
+                assert_eq!(a.is_subset_of(b), expected);
+                assert_eq!(b.is_superset_of(a), expected);
+            }
+        }
+        assert!(0i8.is_subset_of(i8::MIN));
+        assert!(!(-1i8).is_subset_of(i8::MAX));
+    }
+
+    #[test]
+    fn is_superset_of() {
+        assert!(0b0110u8.is_superset_of(0b0010));
+        assert!(!0b0110u8.is_superset_of(0b0011));
+        assert!((-1i64).is_superset_of(i64::MIN));
+    }
+
+    #[test]
+    fn is_disjoint() {
+        assert!(0b0110u8.is_disjoint(0b1001));
+        assert!(!0b0110u8.is_disjoint(0b0011));
+        assert!(i32::MAX.is_disjoint(i32::MIN));
+    }
+
+    #[test]
+    fn cardinality() {
+        assert_eq!(0b0110u8.cardinality(), 2);
+        assert_eq!((-1i64).cardinality(), 64);
+        assert_eq!(0u128.cardinality(), 0);
+    }
+
+    #[test]
+    fn from_indices() {
+        assert_eq!(u64::from_indices(vec![1, 3, 5]), Some(0b101010));
+        assert_eq!(u8::from_indices(vec![1, 1, 1]), Some(0b10));
+        assert_eq!(i8::from_indices(0..8), Some(-1));
+        assert_eq!(u8::from_indices(0..9), None);
+        assert_eq!(u32::from_indices(Vec::new()), Some(0));
+        let set = 0x8421_0001u32;
+        assert_eq!(u32::from_indices(set.iter_ones()), Some(set));
+    }
+
     #[test]
     fn zero() {
         assert_eq!(i8::zero(), 0i8);
@@ -0,0 +1,32 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bound checks shared by the `Bitwise` implementations.
+
+macro_rules! check_bit_index_or_return_none {
+    ($bit:expr, $max_bits: expr) => {
+        if $bit >= $max_bits {
+            return None;
+        }
+    };
+}
+
+macro_rules! check_range_or_return_none {
+    ($range:expr, $max_bits: expr) => {
+        let (start, end) = $crate::range_to_bounds(&$range, $max_bits);
+        if start > end || end > $max_bits {
+            return None;
+        }
+    };
+}
+
+macro_rules! check_field_or_return_none {
+    ($offset:expr, $width:expr, $max_bits: expr) => {
+        if $offset > $max_bits || $width > $max_bits - $offset {
+            return None;
+        }
+    };
+}
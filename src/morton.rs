@@ -0,0 +1,223 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Morton (Z-order) encoding and decoding in 2D and 3D.
+//!
+//! The bits of the coordinates are interleaved starting from the least significant
+//! bit of `x`. The code type is chosen at compile time from the coordinate type:
+//!
+//! | Coordinate | 2D code | 3D code |
+//! |------------|---------|---------|
+//! | `u8`       | `u16`   | `u32`   |
+//! | `u16`      | `u32`   | `u64`   |
+//! | `u32`      | `u64`   | `u128`  |
+//! | `u64`      | `u128`  |         |
+
+use crate::Bitwise;
+
+/// Coordinate types that can be interleaved into a 2D Morton code
+pub trait Morton2d: Bitwise {
+    /// The type of the Morton code, twice as wide as the coordinate
+    type Code: Bitwise;
+
+    /// Interleaves the bits of `x` and `y`, with `x` in the even positions
+    fn morton_encode_2d(x: Self, y: Self) -> Self::Code;
+
+    /// Splits a Morton code into its `(x, y)` coordinates
+    fn morton_decode_2d(code: Self::Code) -> (Self, Self);
+}
+
+/// Coordinate types that can be interleaved into a 3D Morton code
+pub trait Morton3d: Bitwise {
+    /// The type of the Morton code, at least three times as wide as the coordinate
+    type Code: Bitwise;
+
+    /// Interleaves the bits of `x`, `y` and `z`, with `x` in the positions multiple of three
+    fn morton_encode_3d(x: Self, y: Self, z: Self) -> Self::Code;
+
+    /// Splits a Morton code into its `(x, y, z)` coordinates
+    fn morton_decode_3d(code: Self::Code) -> (Self, Self, Self);
+}
+
+/// Interleaves the bits of `x` and `y` into a 2D Morton code
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::morton::morton_encode_2d;
+///
+/// let code: u32 = morton_encode_2d(0b11u16, 0b01u16);
+/// assert_eq!(code, 0b0111);
+/// ```
+pub fn morton_encode_2d<T: Morton2d>(x: T, y: T) -> T::Code {
+    T::morton_encode_2d(x, y)
+}
+
+/// Splits a 2D Morton code into its `(x, y)` coordinates
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::morton::morton_decode_2d;
+///
+/// let (x, y): (u16, u16) = morton_decode_2d(0b0111u32);
+/// assert_eq!((x, y), (0b11, 0b01));
+/// ```
+pub fn morton_decode_2d<T: Morton2d>(code: T::Code) -> (T, T) {
+    T::morton_decode_2d(code)
+}
+
+/// Interleaves the bits of `x`, `y` and `z` into a 3D Morton code
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::morton::morton_encode_3d;
+///
+/// let code: u64 = morton_encode_3d(0b11u16, 0b01u16, 0b10u16);
+/// assert_eq!(code, 0b101_011);
+/// ```
+pub fn morton_encode_3d<T: Morton3d>(x: T, y: T, z: T) -> T::Code {
+    T::morton_encode_3d(x, y, z)
+}
+
+/// Splits a 3D Morton code into its `(x, y, z)` coordinates
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::morton::morton_decode_3d;
+///
+/// let (x, y, z): (u16, u16, u16) = morton_decode_3d(0b101_011u64);
+/// assert_eq!((x, y, z), (0b11, 0b01, 0b10));
+/// ```
+pub fn morton_decode_3d<T: Morton3d>(code: T::Code) -> (T, T, T) {
+    T::morton_decode_3d(code)
+}
+
+macro_rules! impl_morton_2d {
+    ($($t:ident => $code:ident),*) => {$(
+        impl Morton2d for $t {
+            type Code = $code;
+
+            fn morton_encode_2d(x: Self, y: Self) -> $code {
+                const X_MASK: $code = !0 / 3;
+                Bitwise::deposit_bits(x as $code, X_MASK) | Bitwise::deposit_bits(y as $code, X_MASK << 1)
+            }
+
+            fn morton_decode_2d(code: $code) -> (Self, Self) {
+                const X_MASK: $code = !0 / 3;
+                (
+                    Bitwise::extract_bits(code, X_MASK) as Self,
+                    Bitwise::extract_bits(code, X_MASK << 1) as Self,
+                )
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_morton_3d {
+    ($($t:ident => $code:ident, $mask:expr),*) => {$(
+        impl Morton3d for $t {
+            type Code = $code;
+
+            fn morton_encode_3d(x: Self, y: Self, z: Self) -> $code {
+                const X_MASK: $code = $mask;
+                Bitwise::deposit_bits(x as $code, X_MASK)
+                    | Bitwise::deposit_bits(y as $code, X_MASK << 1)
+                    | Bitwise::deposit_bits(z as $code, X_MASK << 2)
+            }
+
+            fn morton_decode_3d(code: $code) -> (Self, Self, Self) {
+                const X_MASK: $code = $mask;
+                (
+                    Bitwise::extract_bits(code, X_MASK) as Self,
+                    Bitwise::extract_bits(code, X_MASK << 1) as Self,
+                    Bitwise::extract_bits(code, X_MASK << 2) as Self,
+                )
+            }
+        }
+    )*};
+}
+
+#[cfg(all(feature = "u8", feature = "u16"))]
+impl_morton_2d!(u8 => u16);
+
+#[cfg(all(feature = "u16", feature = "u32"))]
+impl_morton_2d!(u16 => u32);
+
+#[cfg(all(feature = "u32", feature = "u64"))]
+impl_morton_2d!(u32 => u64);
+
+#[cfg(all(feature = "u64", feature = "u128"))]
+impl_morton_2d!(u64 => u128);
+
+#[cfg(all(feature = "u8", feature = "u32"))]
+impl_morton_3d!(u8 => u32, 0x0024_9249);
+
+#[cfg(all(feature = "u16", feature = "u64"))]
+impl_morton_3d!(u16 => u64, 0x2492_4924_9249);
+
+#[cfg(all(feature = "u32", feature = "u128"))]
+impl_morton_3d!(u32 => u128, 0x2492_4924_9249_2492_4924_9249);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interleave(coordinates: &[u64], bits: usize) -> u128 {
+        let mut code = 0u128;
+        for bit in 0..bits {
+            for (axis, coordinate) in coordinates.iter().enumerate() {
+                code |= ((coordinate >> bit & 1) as u128) << (bit * coordinates.len() + axis);
+            }
+        }
+        code
+    }
+
+    #[test]
+    fn morton_2d() {
+        assert_eq!(morton_encode_2d(0u8, 0u8), 0u16);
+        assert_eq!(morton_encode_2d(u8::MAX, 0), 0x5555u16);
+        assert_eq!(morton_encode_2d(0, u8::MAX), 0xAAAAu16);
+        assert_eq!(morton_encode_2d(u64::MAX, u64::MAX), u128::MAX);
+        assert_eq!(morton_decode_2d::<u32>(0xAAAA_AAAA_0000_0003), (1, 0xFFFF_0001));
+    }
+
+    #[test]
+    fn morton_3d() {
+        assert_eq!(morton_encode_3d(u8::MAX, 0, 0), 0x0024_9249u32);
+        assert_eq!(morton_encode_3d(0, 0, u8::MAX), 0x0024_9249u32 << 2);
+        assert_eq!(morton_encode_3d(u32::MAX, u32::MAX, u32::MAX), u128::MAX >> 32);
+        assert_eq!(morton_decode_3d::<u16>(0b111_100_010_001), (0b1001, 0b1010, 0b1100));
+    }
+
+    #[test]
+    fn morton_matches_interleave() {
+        let samples = [0u64, 1, 2, 3, 0x55, 0xA5, 0xFF, 0x1234, 0xFFFF, 0xDEAD_BEEF, u64::MAX];
+        for &x in &samples {
+            for &y in &samples {
+                let code = morton_encode_2d(x as u16, y as u16);
+                assert_eq!(code as u128, interleave(&[x as u16 as u64, y as u16 as u64], 16));
+                assert_eq!(morton_decode_2d::<u16>(code), (x as u16, y as u16));
+
+                let code = morton_encode_2d(x, y);
+                assert_eq!(code, interleave(&[x, y], 64));
+                assert_eq!(morton_decode_2d::<u64>(code), (x, y));
+
+                for &z in &samples {
+                    let code = morton_encode_3d(x as u8, y as u8, z as u8);
+                    assert_eq!(code as u128, interleave(&[x as u8 as u64, y as u8 as u64, z as u8 as u64], 8));
+                    assert_eq!(morton_decode_3d::<u8>(code), (x as u8, y as u8, z as u8));
+
+                    let code = morton_encode_3d(x as u32, y as u32, z as u32);
+                    assert_eq!(code, interleave(&[x as u32 as u64, y as u32 as u64, z as u32 as u64], 32));
+                    assert_eq!(morton_decode_3d::<u32>(code), (x as u32, y as u32, z as u32));
+                }
+            }
+        }
+    }
+}
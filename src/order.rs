@@ -0,0 +1,208 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Canonical orders of masks seen as sets of bit positions.
+//!
+//! Every order covers all the `2^bit_size()` masks of a type. The sign bit of the
+//! signed integers is just the highest position, so `-1i8` is the set `{0, ..., 7}`.
+
+use std::cmp::Ordering;
+
+use crate::Bitwise;
+
+/// An order of the masks seen as sets of bit positions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SetOrder {
+    /// Colexicographic order: the set whose largest differing element is larger comes last.
+    /// This is the numeric order of the masks read as unsigned numbers.
+    Colex,
+    /// Lexicographic order of the elements sorted from the lowest, where a set comes
+    /// before the sets that extend it: `{} < {0} < {0, 1} < {0, 2} < {1} < {1, 2} < {2}`.
+    Lex,
+    /// Order by the number of elements, breaking ties with the colexicographic order.
+    Popcount,
+}
+
+pub(crate) fn cmp_in_order<T: Bitwise>(a: T, b: T, order: SetOrder) -> Ordering {
+    match order {
+        SetOrder::Colex => cmp_colex(a, b),
+        SetOrder::Lex => cmp_lex(a, b),
+        SetOrder::Popcount => a
+            .count_ones_in_range_unchecked(..)
+            .cmp(&b.count_ones_in_range_unchecked(..))
+            .then_with(|| cmp_colex(a, b)),
+    }
+}
+
+pub(crate) fn next_in_order<T: Bitwise>(value: T, order: SetOrder) -> Option<T> {
+    match order {
+        SetOrder::Colex => next_colex(value),
+        SetOrder::Lex => next_lex(value),
+        SetOrder::Popcount => next_popcount(value),
+    }
+}
+
+pub(crate) fn prev_in_order<T: Bitwise>(value: T, order: SetOrder) -> Option<T> {
+    match order {
+        SetOrder::Colex => prev_colex(value),
+        SetOrder::Lex => prev_lex(value),
+        SetOrder::Popcount => prev_popcount(value),
+    }
+}
+
+fn cmp_colex<T: Bitwise>(a: T, b: T) -> Ordering {
+    match (a ^ b).highest_set_bit() {
+        None => Ordering::Equal,
+        Some(index) if b.get_bit_unchecked(index) => Ordering::Less,
+        Some(_) => Ordering::Greater,
+    }
+}
+
+fn cmp_lex<T: Bitwise>(a: T, b: T) -> Ordering {
+    // Both sequences agree below the lowest differing element `index`. The set holding it
+    // comes first, unless the other set has nothing above it and is therefore a prefix.
+    match (a ^ b).lowest_set_bit() {
+        None => Ordering::Equal,
+        Some(index) => {
+            let (with, without) = if a.get_bit_unchecked(index) { (a, b) } else { (b, a) };
+            let with_first = without.next_set_bit(index).is_some();
+            match (with == a, with_first) {
+                (true, true) | (false, false) => Ordering::Less,
+                _ => Ordering::Greater,
+            }
+        }
+    }
+}
+
+fn next_colex<T: Bitwise>(value: T) -> Option<T> {
+    let index = value.lowest_clear_bit()?;
+    Some(value.clear_range_unchecked(..index).set_bit_unchecked(index))
+}
+
+fn prev_colex<T: Bitwise>(value: T) -> Option<T> {
+    let index = value.lowest_set_bit()?;
+    Some(value.clear_bit_unchecked(index).set_range_unchecked(..index))
+}
+
+fn next_lex<T: Bitwise>(value: T) -> Option<T> {
+    let last = T::bit_size() - 1;
+    match value.highest_set_bit() {
+        None => Some(T::one()),
+        Some(highest) if highest < last => Some(value.set_bit_unchecked(highest + 1)),
+        Some(_) => {
+            let rest = value.clear_bit_unchecked(last);
+            let highest = rest.highest_set_bit()?;
+            Some(rest.clear_bit_unchecked(highest).set_bit_unchecked(highest + 1))
+        }
+    }
+}
+
+fn prev_lex<T: Bitwise>(value: T) -> Option<T> {
+    let highest = value.highest_set_bit()?;
+    let rest = value.clear_bit_unchecked(highest);
+    if highest == 0 || rest.get_bit_unchecked(highest - 1) {
+        return Some(rest);
+    }
+    Some(rest.set_bit_unchecked(highest - 1).set_bit_unchecked(T::bit_size() - 1))
+}
+
+fn next_popcount<T: Bitwise>(value: T) -> Option<T> {
+    if let Some(next) = value.next_same_popcount() {
+        return Some(next);
+    }
+    let ones = value.count_ones_in_range_unchecked(..);
+    if ones == T::bit_size() {
+        return None;
+    }
+    Some(T::get_range_mask_unchecked(..=ones))
+}
+
+fn prev_popcount<T: Bitwise>(value: T) -> Option<T> {
+    // Complementing reverses the colexicographic order and swaps `k` and `bit_size() - k`.
+    if let Some(next) = value.flip().next_same_popcount() {
+        return Some(next.flip());
+    }
+    let ones = value.count_ones_in_range_unchecked(..);
+    if ones == 0 {
+        return None;
+    }
+    Some(T::get_range_mask_unchecked(T::bit_size() + 1 - ones..))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::SetOrder;
+    use crate::Bitwise;
+
+    const ORDERS: [SetOrder; 3] = [SetOrder::Colex, SetOrder::Lex, SetOrder::Popcount];
+
+    fn elements(mask: u8) -> Vec<usize> {
+        mask.iter_ones().collect()
+    }
+
+    fn brute_force(order: SetOrder) -> Vec<u8> {
+        let mut masks: Vec<u8> = (0..=u8::MAX).collect();
+        match order {
+            SetOrder::Colex => {}
+            SetOrder::Lex => masks.sort_by_key(|&mask| elements(mask)),
+            SetOrder::Popcount => masks.sort_by_key(|&mask| (mask.count_ones(), mask)),
+        }
+        masks
+    }
+
+    #[test]
+    fn cmp_in_order() {
+        for &order in &ORDERS {
+            let sorted = brute_force(order);
+            for (i, &a) in sorted.iter().enumerate() {
+                for (j, &b) in sorted.iter().enumerate() {
+                    assert_eq!(a.cmp_in_order(b, order), i.cmp(&j));
+                    let (a, b) = (a as i8, b as i8);
+                    assert_eq!(a.cmp_in_order(b, order), i.cmp(&j));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn next_in_order() {
+        for &order in &ORDERS {
+            let sorted = brute_force(order);
+            for pair in sorted.windows(2) {
+                assert_eq!(pair[0].next_in_order(order), Some(pair[1]));
+                assert_eq!((pair[0] as i8).next_in_order(order), Some(pair[1] as i8));
+            }
+            assert_eq!(sorted[255].next_in_order(order), None);
+        }
+    }
+
+    #[test]
+    fn prev_in_order() {
+        for &order in &ORDERS {
+            let sorted = brute_force(order);
+            for pair in sorted.windows(2) {
+                assert_eq!(pair[1].prev_in_order(order), Some(pair[0]));
+                assert_eq!((pair[1] as i8).prev_in_order(order), Some(pair[0] as i8));
+            }
+            assert_eq!(sorted[0].prev_in_order(order), None);
+        }
+    }
+
+    #[test]
+    fn orders_on_wide_types() {
+        assert_eq!(u128::MAX.next_in_order(SetOrder::Colex), None);
+        assert_eq!((1u128 << 127).next_in_order(SetOrder::Lex), None);
+        assert_eq!(0u128.prev_in_order(SetOrder::Lex), None);
+        assert_eq!(0u128.next_in_order(SetOrder::Lex), Some(1));
+        assert_eq!((1u64 << 63).next_in_order(SetOrder::Popcount), Some(0b11));
+        assert_eq!(0b11u64.prev_in_order(SetOrder::Popcount), Some(1 << 63));
+        assert_eq!((-1i32).cmp_in_order(0, SetOrder::Colex), Ordering::Greater);
+        assert_eq!(0b10u16.cmp_in_order(0b011, SetOrder::Lex), Ordering::Greater);
+        assert_eq!(0b100u16.cmp_in_order(0b011, SetOrder::Popcount), Ordering::Less);
+    }
+}
@@ -0,0 +1,386 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Growable vectors of integers stored with a fixed number of bits each.
+
+use std::iter::{FromIterator, FusedIterator};
+
+use crate::Bitwise;
+
+const WORD_BITS: usize = 64;
+
+/// A growable vector of unsigned integers of `width` bits each, packed one after the other
+/// into `u64` words, so a value may straddle two words
+///
+/// **Note:** The width is chosen at runtime, between 0 and 64 bits. The unused bits of the
+/// last word are always turned off.
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::PackedVec;
+///
+/// let mut values = PackedVec::from_slice(&[3, 1, 4, 1, 5]);
+/// assert_eq!(values.width(), 3);
+/// assert_eq!(values.as_words(), &[0b101_001_100_001_011]);
+/// values.push(7);
+/// values.set(1, 6);
+/// assert_eq!(values.iter().collect::<Vec<_>>(), [3, 6, 4, 1, 5, 7]);
+/// assert_eq!(values.repack(2), None);
+/// assert_eq!(values.repack(10).unwrap().get(5), Some(7));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PackedVec {
+    words: Vec<u64>,
+    width: usize,
+    len: usize,
+}
+
+impl PackedVec {
+    /// Creates an empty vector of integers of `width` bits
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is greater than 64.
+    pub fn new(width: usize) -> Self {
+        PackedVec::with_capacity(width, 0)
+    }
+
+    /// Creates an empty vector of integers of `width` bits with room for at least `capacity`
+    /// integers
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is greater than 64 or if `capacity` integers of `width` bits overflow
+    /// a `usize` number of bits.
+    pub fn with_capacity(width: usize, capacity: usize) -> Self {
+        assert!(width <= WORD_BITS, "the width (is {}) should be <= 64", width);
+        let bits = capacity.checked_mul(width).expect("capacity overflow");
+        PackedVec { words: Vec::with_capacity(Self::words_for(bits)), width, len: 0 }
+    }
+
+    /// Creates a vector holding `values`, with the smallest width that fits all of them
+    pub fn from_slice(values: &[u64]) -> Self {
+        let width = values.iter().map(|&value| Self::width_of(value)).max().unwrap_or(0);
+        let mut packed = PackedVec::with_capacity(width, values.len());
+        packed.extend(values.iter().copied());
+        packed
+    }
+
+    /// Returns the number of bits of each integer
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of integers in the vector
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` when the vector holds no integers
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the words that store the integers, the first integer at the least significant
+    /// bits of the first word
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Returns the integer at `index`, or `None` if it is out of bounds
+    pub fn get(&self, index: usize) -> Option<u64> {
+        if index >= self.len {
+            return None;
+        }
+        Some(self.read(index))
+    }
+
+    /// Replaces the integer at `index` with `value`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds or if `value` does not fit in `width()` bits.
+    pub fn set(&mut self, index: usize, value: u64) {
+        assert!(index < self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+        self.check_fits(value);
+        self.write(index, value);
+    }
+
+    /// Appends `value` to the back of the vector
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` does not fit in `width()` bits.
+    pub fn push(&mut self, value: u64) {
+        self.check_fits(value);
+        self.len += 1;
+        self.words.resize(Self::words_for(self.len * self.width), 0);
+        self.write(self.len - 1, value);
+    }
+
+    /// Removes the last integer and returns it, or `None` if the vector is empty
+    pub fn pop(&mut self) -> Option<u64> {
+        let value = self.get(self.len.checked_sub(1)?)?;
+        self.write(self.len - 1, 0);
+        self.len -= 1;
+        self.words.truncate(Self::words_for(self.len * self.width));
+        Some(value)
+    }
+
+    /// Returns an iterator over the integers of the vector
+    pub fn iter(&self) -> PackedIter<'_> {
+        PackedIter { packed: self, front: 0, back: self.len }
+    }
+
+    /// Returns a copy of the vector that stores the integers with `width` bits each, or `None`
+    /// if some integer does not fit in `width` bits
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is greater than 64.
+    pub fn repack(&self, width: usize) -> Option<Self> {
+        let mut packed = PackedVec::with_capacity(width, self.len);
+        for value in self.iter() {
+            if Self::width_of(value) > width {
+                return None;
+            }
+            packed.push(value);
+        }
+        Some(packed)
+    }
+
+    fn read(&self, index: usize) -> u64 {
+        if self.width == 0 {
+            return 0;
+        }
+        let (word, offset) = Self::locate(index * self.width);
+        let next = self.words.get(word + 1).copied().unwrap_or(0);
+        Bitwise::funnel_shr(next, self.words[word], offset).extract_field_unchecked(0, self.width)
+    }
+
+    fn write(&mut self, index: usize, value: u64) {
+        if self.width == 0 {
+            return;
+        }
+        let (word, offset) = Self::locate(index * self.width);
+        let low = self.width.min(WORD_BITS - offset);
+        self.words[word] = self.words[word].insert_field_unchecked(offset, low, value);
+        if low < self.width {
+            let high = value.extract_field_unchecked(low, self.width - low);
+            self.words[word + 1] = self.words[word + 1].insert_field_unchecked(0, self.width - low, high);
+        }
+    }
+
+    fn check_fits(&self, value: u64) {
+        assert!(
+            Self::width_of(value) <= self.width,
+            "the value (is {}) should fit in {} bits",
+            value,
+            self.width
+        );
+    }
+
+    fn width_of(value: u64) -> usize {
+        value.highest_set_bit().map_or(0, |bit| bit + 1)
+    }
+
+    fn locate(bit: usize) -> (usize, usize) {
+        (bit / WORD_BITS, bit % WORD_BITS)
+    }
+
+    fn words_for(bits: usize) -> usize {
+        (bits + WORD_BITS - 1) / WORD_BITS
+    }
+}
+
+impl Extend<u64> for PackedVec {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, values: I) {
+        for value in values {
+            self.push(value);
+        }
+    }
+}
+
+impl FromIterator<u64> for PackedVec {
+    fn from_iter<I: IntoIterator<Item = u64>>(values: I) -> Self {
+        PackedVec::from_slice(&values.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<'a> IntoIterator for &'a PackedVec {
+    type Item = u64;
+    type IntoIter = PackedIter<'a>;
+
+    fn into_iter(self) -> PackedIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the integers of a [`PackedVec`], returned by [`PackedVec::iter`]
+#[derive(Clone, Debug)]
+pub struct PackedIter<'a> {
+    packed: &'a PackedVec,
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for PackedIter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.packed.read(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for PackedIter<'_> {
+    fn next_back(&mut self) -> Option<u64> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.packed.read(self.back))
+    }
+}
+
+impl ExactSizeIterator for PackedIter<'_> {}
+
+impl FusedIterator for PackedIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedVec;
+
+    fn pattern(len: usize, width: usize) -> Vec<u64> {
+        let mask = if width == 64 { u64::MAX } else { (1 << width) - 1 };
+        (0..len as u64).map(|index| index.wrapping_mul(0x9E37_79B9_7F4A_7C15) & mask).collect()
+    }
+
+    fn check_invariant(packed: &PackedVec) {
+        let bits = packed.len() * packed.width();
+        assert_eq!(packed.as_words().len(), (bits + 63) / 64);
+        if let (Some(&last), true) = (packed.as_words().last(), bits % 64 != 0) {
+            assert_eq!(last >> (bits % 64), 0);
+        }
+    }
+
+    #[test]
+    fn matches_vec_of_u64() {
+        for width in 0..=64 {
+            let expected = pattern(40, width);
+            let mut packed = PackedVec::new(width);
+            packed.extend(expected.iter().copied());
+            assert_eq!(packed.len(), 40);
+            assert_eq!(packed.iter().collect::<Vec<_>>(), expected);
+            check_invariant(&packed);
+            let mut model = expected.clone();
+            for index in (0..40).step_by(3) {
+                let value = expected[39 - index];
+                packed.set(index, value);
+                model[index] = value;
+            }
+            assert_eq!(packed.iter().collect::<Vec<_>>(), model);
+            assert_eq!(packed.get(40), None);
+            check_invariant(&packed);
+            while let Some(value) = packed.pop() {
+                assert_eq!(Some(value), model.pop());
+                check_invariant(&packed);
+            }
+            assert_eq!(packed, PackedVec::new(width));
+        }
+    }
+
+    #[test]
+    fn values_straddle_words() {
+        let mut packed = PackedVec::new(60);
+        packed.push(0x0FFF_FFFF_FFFF_FFFF);
+        packed.push(0x0123_4567_89AB_CDEF);
+        assert_eq!(packed.as_words(), &[0xFFFF_FFFF_FFFF_FFFF, 0x0012_3456_789A_BCDE]);
+        packed.set(0, 0);
+        assert_eq!(packed.get(1), Some(0x0123_4567_89AB_CDEF));
+        assert_eq!(packed.as_words(), &[0xF000_0000_0000_0000, 0x0012_3456_789A_BCDE]);
+    }
+
+    #[test]
+    fn from_slice_picks_the_width() {
+        assert_eq!(PackedVec::from_slice(&[]).width(), 0);
+        assert_eq!(PackedVec::from_slice(&[0, 0]).width(), 0);
+        assert_eq!(PackedVec::from_slice(&[0, 0]).get(1), Some(0));
+        assert_eq!(PackedVec::from_slice(&[1, 0]).width(), 1);
+        assert_eq!(PackedVec::from_slice(&[255, 3]).width(), 8);
+        assert_eq!(PackedVec::from_slice(&[256]).width(), 9);
+        assert_eq!(PackedVec::from_slice(&[u64::MAX]).width(), 64);
+        let packed: PackedVec = vec![5, 2, 9].into_iter().collect();
+        assert_eq!((packed.width(), packed.len()), (4, 3));
+    }
+
+    #[test]
+    fn repack() {
+        let values = pattern(50, 13);
+        let packed = PackedVec::from_slice(&values);
+        for width in 13..=64 {
+            let repacked = packed.repack(width).unwrap();
+            assert_eq!(repacked.width(), width);
+            assert_eq!(repacked.iter().collect::<Vec<_>>(), values);
+            check_invariant(&repacked);
+            assert_eq!(repacked.repack(packed.width()), Some(packed.clone()));
+        }
+        assert_eq!(packed.repack(12), None);
+        assert_eq!(PackedVec::from_slice(&[1, 2, 3]).repack(2).unwrap().as_words(), &[0b11_10_01]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the value (is 8) should fit in 3 bits")]
+    fn push_too_wide() {
+        PackedVec::new(3).push(8);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn set_out_of_bounds() {
+        PackedVec::new(3).set(0, 1);
+    }
+
+    #[test]
+    fn iter_from_both_ends() {
+        let values = pattern(30, 11);
+        let packed = PackedVec::from_slice(&values);
+        let mut iter = packed.iter();
+        assert_eq!(iter.len(), 30);
+        assert_eq!(iter.next_back(), values.last().copied());
+        assert_eq!(iter.next(), values.first().copied());
+        assert_eq!(iter.len(), 28);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), values[1..29].iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!((&packed).into_iter().collect::<Vec<_>>(), values);
+        let mut sum = 0;
+        for value in &packed {
+            sum += value;
+        }
+        assert_eq!(sum, values.iter().sum());
+        assert_eq!(PackedVec::new(5).iter().next_back(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn capacity_overflow() {
+        PackedVec::with_capacity(64, usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "the width (is 65) should be <= 64")]
+    fn width_too_large() {
+        PackedVec::new(65);
+    }
+}
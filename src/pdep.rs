@@ -0,0 +1,172 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Parallel bit deposit and extract, with the semantics of the x86 BMI2
+//! `PDEP` and `PEXT` instructions.
+
+use crate::Bitwise;
+
+/// Portable implementation of `PDEP`, with a cost proportional to the number
+/// of bits turned on in `mask`.
+pub(crate) fn deposit_bits<T: Bitwise>(value: T, mask: T) -> T {
+    mask.iter_ones()
+        .enumerate()
+        .filter(|&(from, _)| value.get_bit_unchecked(from))
+        .fold(T::zero(), |result, (_, to)| result.set_bit_unchecked(to))
+}
+
+/// Portable implementation of `PEXT`, with a cost proportional to the number
+/// of bits turned on in `mask`.
+pub(crate) fn extract_bits<T: Bitwise>(value: T, mask: T) -> T {
+    mask.iter_ones()
+        .enumerate()
+        .filter(|&(_, from)| value.get_bit_unchecked(from))
+        .fold(T::zero(), |result, (to, _)| result.set_bit_unchecked(to))
+}
+
+/// `PDEP` and `PEXT` through the BMI2 instructions, when the running CPU supports them.
+#[cfg(all(feature = "bmi2", target_arch = "x86_64"))]
+pub(crate) trait Hardware: Sized {
+    /// Returns `None` when the running CPU does not support BMI2.
+    fn deposit_bits(self, mask: Self) -> Option<Self>;
+
+    /// Returns `None` when the running CPU does not support BMI2.
+    fn extract_bits(self, mask: Self) -> Option<Self>;
+}
+
+#[cfg(all(feature = "bmi2", target_arch = "x86_64"))]
+mod hardware {
+    use super::Hardware;
+    use std::arch::x86_64::{_pdep_u32, _pdep_u64, _pext_u32, _pext_u64};
+
+    fn pdep_u64(value: u64, mask: u64) -> u64 {
+        // SAFETY: only called after `is_x86_feature_detected!("bmi2")`.
+        unsafe { _pdep_u64(value, mask) }
+    }
+
+    fn pext_u64(value: u64, mask: u64) -> u64 {
+        // SAFETY: only called after `is_x86_feature_detected!("bmi2")`.
+        unsafe { _pext_u64(value, mask) }
+    }
+
+    fn pdep_u32(value: u32, mask: u32) -> u32 {
+        // SAFETY: only called after `is_x86_feature_detected!("bmi2")`.
+        unsafe { _pdep_u32(value, mask) }
+    }
+
+    fn pext_u32(value: u32, mask: u32) -> u32 {
+        // SAFETY: only called after `is_x86_feature_detected!("bmi2")`.
+        unsafe { _pext_u32(value, mask) }
+    }
+
+    fn pdep_u128(value: u128, mask: u128) -> u128 {
+        let low_mask = mask as u64;
+        let low = pdep_u64(value as u64, low_mask);
+        let high = pdep_u64((value >> low_mask.count_ones()) as u64, (mask >> 64) as u64);
+        (low as u128) | ((high as u128) << 64)
+    }
+
+    fn pext_u128(value: u128, mask: u128) -> u128 {
+        let low_mask = mask as u64;
+        let low = pext_u64(value as u64, low_mask);
+        let high = pext_u64((value >> 64) as u64, (mask >> 64) as u64);
+        (low as u128) | ((high as u128) << low_mask.count_ones())
+    }
+
+    macro_rules! impl_hardware {
+        ($($t:ident => $unsigned:ident as $word:ident, $pdep:ident, $pext:ident);*) => {$(
+            impl Hardware for $t {
+                fn deposit_bits(self, mask: Self) -> Option<Self> {
+                    if !is_x86_feature_detected!("bmi2") {
+                        return None;
+                    }
+                    Some($pdep(self as $unsigned as $word, mask as $unsigned as $word) as Self)
+                }
+
+                fn extract_bits(self, mask: Self) -> Option<Self> {
+                    if !is_x86_feature_detected!("bmi2") {
+                        return None;
+                    }
+                    Some($pext(self as $unsigned as $word, mask as $unsigned as $word) as Self)
+                }
+            }
+        )*};
+    }
+
+    impl_hardware!(
+        i8 => u8 as u32, pdep_u32, pext_u32;
+        i16 => u16 as u32, pdep_u32, pext_u32;
+        i32 => u32 as u32, pdep_u32, pext_u32;
+        i64 => u64 as u64, pdep_u64, pext_u64;
+        i128 => u128 as u128, pdep_u128, pext_u128;
+        u8 => u8 as u32, pdep_u32, pext_u32;
+        u16 => u16 as u32, pdep_u32, pext_u32;
+        u32 => u32 as u32, pdep_u32, pext_u32;
+        u64 => u64 as u64, pdep_u64, pext_u64;
+        u128 => u128 as u128, pdep_u128, pext_u128
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bitwise;
+
+    #[test]
+    fn deposit_bits() {
+        assert_eq!(Bitwise::deposit_bits(0b101u8, 0b11010010), 0b01000010);
+        assert_eq!(Bitwise::deposit_bits(0u8, 0b11010010), 0);
+        assert_eq!(Bitwise::deposit_bits(-1i16, 0x0F0F), 0x0F0F);
+        assert_eq!(Bitwise::deposit_bits(1u128, 1 << 127), 1 << 127);
+        assert_eq!(Bitwise::deposit_bits(u128::MAX, 0xFF << 60), 0xFF << 60);
+    }
+
+    #[test]
+    fn extract_bits() {
+        assert_eq!(Bitwise::extract_bits(0b01000010u8, 0b11010010), 0b101);
+        assert_eq!(Bitwise::extract_bits(i32::MIN, i32::MIN), 1);
+        assert_eq!(Bitwise::extract_bits(-1i64, 0x00F0), 0xF);
+        assert_eq!(Bitwise::extract_bits(1u128 << 127, 1 << 127 | 1), 0b10);
+        assert_eq!(Bitwise::extract_bits(0xAB_u128 << 60, 0xFF << 60), 0xAB);
+    }
+
+    #[test]
+    fn deposit_extract_brute_force() {
+        for mask in 0..=u8::MAX {
+            let positions: Vec<usize> = (0..8).filter(|&i| mask >> i & 1 == 1).collect();
+            for value in 0..=u8::MAX {
+                let mut deposited = 0u8;
+                let mut extracted = 0u8;
+                for (k, &i) in positions.iter().enumerate() {
+                    deposited |= (value >> k & 1) << i;
+                    extracted |= (value >> i & 1) << k;
+                }
+                assert_eq!(Bitwise::deposit_bits(value, mask), deposited);
+                assert_eq!(Bitwise::extract_bits(value, mask), extracted);
+                assert_eq!(Bitwise::deposit_bits(value as i8, mask as i8), deposited as i8);
+                assert_eq!(Bitwise::extract_bits(value as i8, mask as i8), extracted as i8);
+            }
+        }
+    }
+
+    #[test]
+    fn extract_inverts_deposit() {
+        macro_rules! check {
+            ($($t:ident),*) => {$(
+                for &mask in &[0 as $t, 1, !0, 0b0101_0110, !0 << 3, (!0 as $t) ^ 5, (!0 as $t) << ($t::bit_size() / 2)] {
+                    for &value in &[0 as $t, 1, !0, 0b0110_1001, (!0 as $t) >> 1] {
+                        let width = mask.count_ones_in_range_unchecked(..);
+                        let low = value.extract_field_unchecked(0, width);
+                        assert_eq!(Bitwise::extract_bits(Bitwise::deposit_bits(value, mask), mask), low);
+                        assert_eq!(Bitwise::deposit_bits(Bitwise::extract_bits(value, mask), mask), value & mask);
+                        assert_eq!(Bitwise::deposit_bits(value, mask), super::deposit_bits(value, mask));
+                        assert_eq!(Bitwise::extract_bits(value, mask), super::extract_bits(value, mask));
+                    }
+                }
+            )*};
+        }
+        check!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Permutations of slices driven by the bits of their indexes.
+
+/// Reorders `slice` so that the element at index `i` moves to the index whose
+/// `log2(slice.len())` low bits are those of `i` in reverse order, as required
+/// by iterative FFT and NTT implementations
+///
+/// # Panics
+///
+/// Panics when the length of `slice` is not zero or a power of two.
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::permutation::bit_reverse;
+///
+/// let mut values = [0, 1, 2, 3, 4, 5, 6, 7];
+/// bit_reverse(&mut values);
+/// assert_eq!(values, [0, 4, 2, 6, 1, 5, 3, 7]);
+/// ```
+pub fn bit_reverse<T>(slice: &mut [T]) {
+    let len = slice.len();
+    if len <= 2 {
+        return;
+    }
+    assert!(len.is_power_of_two(), "the length of the slice must be a power of two");
+    let shift = len.leading_zeros() + 1;
+    for index in 0..len {
+        let reversed = index.reverse_bits() >> shift;
+        if index < reversed {
+            slice.swap(index, reversed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bit_reverse;
+    use crate::Bitwise;
+
+    #[test]
+    fn bit_reverse_small() {
+        let mut values: [u8; 0] = [];
+        bit_reverse(&mut values);
+
+        let mut values = [7];
+        bit_reverse(&mut values);
+        assert_eq!(values, [7]);
+
+        let mut values = [1, 2];
+        bit_reverse(&mut values);
+        assert_eq!(values, [1, 2]);
+
+        let mut values = [0, 1, 2, 3, 4, 5, 6, 7];
+        bit_reverse(&mut values);
+        assert_eq!(values, [0, 4, 2, 6, 1, 5, 3, 7]);
+    }
+
+    #[test]
+    fn bit_reverse_matches_reverse_low_bits() {
+        for bits in 0..12 {
+            let mut values: Vec<u32> = (0..1 << bits).collect();
+            bit_reverse(&mut values);
+            for (index, &value) in values.iter().enumerate() {
+                assert_eq!(value, (index as u32).reverse_low_bits_unchecked(bits));
+            }
+            bit_reverse(&mut values);
+            assert!(values.iter().enumerate().all(|(index, &value)| value as usize == index));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn bit_reverse_not_power_of_two() {
+        let mut values = [1, 2, 3];
+        bit_reverse(&mut values);
+    }
+}
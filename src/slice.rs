@@ -0,0 +1,391 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Borrowed views of a range of bits inside a slice of `Bitwise` words.
+
+use std::marker::PhantomData;
+use std::ops::{Range, RangeBounds};
+
+use crate::bitorder::{BitOrder, Lsb0};
+use crate::{range_to_bounds, Bitwise};
+
+/// A shared view of a range of bits inside a slice of words, starting at the first word and
+/// at the bit of each word chosen by the bit order `O`
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::BitSlice;
+///
+/// let packet = [0b1000_0000u8, 0b0000_0011, 0xFF];
+/// let flags = BitSlice::new(&packet).slice(7..10).unwrap();
+/// assert_eq!(flags.len(), 3);
+/// assert_eq!(flags.count_ones(), 3);
+/// assert_eq!(flags.get_bit(0), Some(true));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct BitSlice<'a, T, O = Lsb0> {
+    words: &'a [T],
+    start: usize,
+    len: usize,
+    order: PhantomData<O>,
+}
+
+/// A mutable view of a range of bits inside a slice of words, starting at the first word and
+/// at the bit of each word chosen by the bit order `O`
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::BitSliceMut;
+///
+/// let mut packet = [0u8; 3];
+/// let mut bits = BitSliceMut::new(&mut packet);
+/// bits.set_range(6..12).unwrap();
+/// bits.clear_bit(8).unwrap();
+/// assert_eq!(packet, [0b1100_0000, 0b0000_1110, 0]);
+/// ```
+///
+/// With the [`Msb0`](crate::bitorder::Msb0) order the bits are numbered from the most
+/// significant bit of each byte, as in network RFCs:
+///
+/// ```rust
+/// use xbitwise::bitorder::Msb0;
+/// use xbitwise::BitSliceMut;
+///
+/// let mut header = [0u8; 2];
+/// BitSliceMut::with_order(&mut header, Msb0).set_range(4..12).unwrap();
+/// assert_eq!(header, [0b0000_1111, 0b1111_0000]);
+/// ```
+#[derive(Debug)]
+pub struct BitSliceMut<'a, T, O = Lsb0> {
+    words: &'a mut [T],
+    start: usize,
+    len: usize,
+    order: PhantomData<O>,
+}
+
+impl<'a, T: Bitwise> BitSlice<'a, T> {
+    /// Creates a view of all the bits of `words`
+    pub fn new(words: &'a [T]) -> Self {
+        BitSlice::with_order(words, Lsb0)
+    }
+}
+
+impl<'a, T: Bitwise, O: BitOrder> BitSlice<'a, T, O> {
+    /// Creates a view of all the bits of `words`, numbering the bits of each word in the
+    /// given order
+    pub fn with_order(words: &'a [T], _order: O) -> Self {
+        BitSlice { words, start: 0, len: words.len() * T::bit_size(), order: PhantomData }
+    }
+
+    /// Returns the number of bits in the view
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` when the view holds no bits
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit in the index position
+    ///
+    /// **Note:** Returns `None` when `index` is not less than `len()`.
+    pub fn get_bit(&self, index: usize) -> Option<bool> {
+        check_bit_index_or_return_none!(index, self.len);
+        let (word, bit) = locate::<T>(self.start + index);
+        Some(O::arrange(self.words[word]).get_bit_unchecked(bit))
+    }
+
+    /// Returns the number of bits turned on, counting a word at a time
+    pub fn count_ones(&self) -> usize {
+        spans::<T>(self.start, self.start + self.len)
+            .map(|(word, range)| O::arrange(self.words[word]).count_ones_in_range_unchecked(range))
+            .sum()
+    }
+
+    /// Returns an iterator over the positions of the bits turned on, in increasing order
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + 'a {
+        let (words, start) = (self.words, self.start);
+        spans::<T>(start, start + self.len).flat_map(move |(word, range)| {
+            let first = word * T::bit_size() + range.start - start;
+            let bits = O::arrange(words[word]).extract_field_unchecked(range.start, range.end - range.start);
+            bits.iter_ones().map(move |bit| first + bit)
+        })
+    }
+
+    /// Returns a view of a range of the bits of this view
+    ///
+    /// **Note:** Returns `None` when the range is not inside `0..len()`.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Option<BitSlice<'a, T, O>> {
+        check_range_or_return_none!(range, self.len);
+        let (start, end) = range_to_bounds(&range, self.len);
+        Some(BitSlice { words: self.words, start: self.start + start, len: end - start, order: PhantomData })
+    }
+}
+
+impl<'a, T: Bitwise> BitSliceMut<'a, T> {
+    /// Creates a mutable view of all the bits of `words`
+    pub fn new(words: &'a mut [T]) -> Self {
+        BitSliceMut::with_order(words, Lsb0)
+    }
+}
+
+impl<'a, T: Bitwise, O: BitOrder> BitSliceMut<'a, T, O> {
+    /// Creates a mutable view of all the bits of `words`, numbering the bits of each word
+    /// in the given order
+    pub fn with_order(words: &'a mut [T], _order: O) -> Self {
+        let len = words.len() * T::bit_size();
+        BitSliceMut { words, start: 0, len, order: PhantomData }
+    }
+
+    /// Returns a shared view of the same bits
+    pub fn as_bit_slice(&self) -> BitSlice<'_, T, O> {
+        BitSlice { words: self.words, start: self.start, len: self.len, order: PhantomData }
+    }
+
+    /// Returns the number of bits in the view
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` when the view holds no bits
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit in the index position
+    ///
+    /// **Note:** Returns `None` when `index` is not less than `len()`.
+    pub fn get_bit(&self, index: usize) -> Option<bool> {
+        self.as_bit_slice().get_bit(index)
+    }
+
+    /// Turns on the bit in the index position
+    ///
+    /// **Note:** Returns `None` and leaves the bits untouched when `index` is not less
+    /// than `len()`.
+    pub fn set_bit(&mut self, index: usize) -> Option<()> {
+        self.update_bit(index, true)
+    }
+
+    /// Turns off the bit in the index position
+    ///
+    /// **Note:** Returns `None` and leaves the bits untouched when `index` is not less
+    /// than `len()`.
+    pub fn clear_bit(&mut self, index: usize) -> Option<()> {
+        self.update_bit(index, false)
+    }
+
+    /// Turns on all the bits in the range, a word at a time
+    ///
+    /// **Note:** Returns `None` and leaves the bits untouched when the range is not inside
+    /// `0..len()`.
+    pub fn set_range<R: RangeBounds<usize>>(&mut self, range: R) -> Option<()> {
+        self.update_range(range, true)
+    }
+
+    /// Turns off all the bits in the range, a word at a time
+    ///
+    /// **Note:** Returns `None` and leaves the bits untouched when the range is not inside
+    /// `0..len()`.
+    pub fn clear_range<R: RangeBounds<usize>>(&mut self, range: R) -> Option<()> {
+        self.update_range(range, false)
+    }
+
+    /// Returns the number of bits turned on, counting a word at a time
+    pub fn count_ones(&self) -> usize {
+        self.as_bit_slice().count_ones()
+    }
+
+    /// Returns an iterator over the positions of the bits turned on, in increasing order
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.as_bit_slice().iter_ones()
+    }
+
+    /// Returns a shared view of a range of the bits of this view
+    ///
+    /// **Note:** Returns `None` when the range is not inside `0..len()`.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Option<BitSlice<'_, T, O>> {
+        self.as_bit_slice().slice(range)
+    }
+
+    /// Returns a mutable view of a range of the bits of this view
+    ///
+    /// **Note:** Returns `None` when the range is not inside `0..len()`.
+    pub fn slice_mut<R: RangeBounds<usize>>(&mut self, range: R) -> Option<BitSliceMut<'_, T, O>> {
+        check_range_or_return_none!(range, self.len);
+        let (start, end) = range_to_bounds(&range, self.len);
+        Some(BitSliceMut { words: self.words, start: self.start + start, len: end - start, order: PhantomData })
+    }
+
+    fn update_bit(&mut self, index: usize, value: bool) -> Option<()> {
+        check_bit_index_or_return_none!(index, self.len);
+        let (word, bit) = locate::<T>(self.start + index);
+        self.words[word] = O::arrange(O::arrange(self.words[word]).update_bit_unchecked(bit, value));
+        Some(())
+    }
+
+    fn update_range<R: RangeBounds<usize>>(&mut self, range: R, value: bool) -> Option<()> {
+        check_range_or_return_none!(range, self.len);
+        let (start, end) = range_to_bounds(&range, self.len);
+        for (word, range) in spans::<T>(self.start + start, self.start + end) {
+            self.words[word] = O::arrange(O::arrange(self.words[word]).update_range_unchecked(range, value));
+        }
+        Some(())
+    }
+}
+
+/// Returns the word holding the bit in the index position and the position inside it.
+fn locate<T: Bitwise>(index: usize) -> (usize, usize) {
+    (index / T::bit_size(), index % T::bit_size())
+}
+
+/// Splits the bits `start..end` into the words they cover and the range inside each word.
+fn spans<T: Bitwise>(start: usize, end: usize) -> impl Iterator<Item = (usize, Range<usize>)> {
+    let bits = T::bit_size();
+    let words = if start < end { start / bits..(end - 1) / bits + 1 } else { 0..0 };
+    words.map(move |word| {
+        let base = word * bits;
+        (word, start.max(base) - base..end.min(base + bits) - base)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitSlice, BitSliceMut};
+    use crate::bitorder::Msb0;
+
+    fn model(words: &[u8]) -> Vec<bool> {
+        (0..words.len() * 8).map(|index| words[index / 8] >> (index % 8) & 1 == 1).collect()
+    }
+
+    const WORDS: [u8; 5] = [0b1010_0110, 0xFF, 0, 0b1000_0001, 0b0111_1110];
+
+    #[test]
+    fn get_bit() {
+        let bits = model(&WORDS);
+        for start in 0..40 {
+            let slice = BitSlice::new(&WORDS).slice(start..).unwrap();
+            for index in 0..slice.len() {
+                assert_eq!(slice.get_bit(index), Some(bits[start + index]));
+            }
+            assert_eq!(slice.get_bit(slice.len()), None);
+        }
+    }
+
+    #[test]
+    fn count_ones() {
+        let bits = model(&WORDS);
+        for start in 0..=40 {
+            for end in start..=40 {
+                let slice = BitSlice::new(&WORDS).slice(start..end).unwrap();
+                assert_eq!(slice.count_ones(), bits[start..end].iter().filter(|&&bit| bit).count());
+            }
+        }
+    }
+
+    #[test]
+    fn iter_ones() {
+        let bits = model(&WORDS);
+        for start in 0..=40 {
+            for end in start..=40 {
+                let slice = BitSlice::new(&WORDS).slice(start..end).unwrap();
+                let expected: Vec<usize> = (0..end - start).filter(|&index| bits[start + index]).collect();
+                assert_eq!(slice.iter_ones().collect::<Vec<_>>(), expected);
+            }
+        }
+        let wide = [1u64 << 63, 1];
+        assert_eq!(BitSlice::new(&wide).iter_ones().collect::<Vec<_>>(), [63, 64]);
+    }
+
+    #[allow(clippy::reversed_empty_ranges)]
+    #[test]
+    fn slice() {
+        let slice = BitSlice::new(&WORDS).slice(4..36).unwrap();
+        assert_eq!(slice.len(), 32);
+        let nested = slice.slice(4..=11).unwrap();
+        assert_eq!((nested.len(), nested.count_ones()), (8, 8));
+        assert!(slice.slice(..33).is_none());
+        assert!(slice.slice(5..4).is_none());
+        assert!(slice.slice(32..).unwrap().is_empty());
+    }
+
+    #[test]
+    fn set_and_clear_bit() {
+        let mut words = [0u16; 2];
+        let mut bits = BitSliceMut::new(&mut words);
+        let mut flags = bits.slice_mut(10..20).unwrap();
+        assert_eq!(flags.set_bit(5), Some(()));
+        assert_eq!(flags.set_bit(9), Some(()));
+        assert_eq!(flags.set_bit(10), None);
+        assert_eq!(flags.get_bit(5), Some(true));
+        assert_eq!(flags.clear_bit(9), Some(()));
+        assert_eq!(words, [1 << 15, 0]);
+    }
+
+    #[test]
+    fn set_and_clear_range() {
+        for start in 0..=40 {
+            for end in start..=40 {
+                let mut words = WORDS;
+                let mut expected = model(&WORDS);
+                let mut view = BitSliceMut::new(&mut words);
+                let mut inner = view.slice_mut(3..).unwrap();
+                if end + 3 <= 40 {
+                    inner.set_range(start..end).unwrap();
+                    for bit in &mut expected[start + 3..end + 3] {
+                        *bit = true;
+                    }
+                    inner.clear_range(start / 2..start).unwrap();
+                    for bit in &mut expected[start / 2 + 3..start + 3] {
+                        *bit = false;
+                    }
+                } else {
+                    assert_eq!(inner.set_range(start..end), None);
+                }
+                assert_eq!(model(&words), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn msb0_order() {
+        let msb0 = |words: &[u8]| -> Vec<bool> {
+            (0..words.len() * 8).map(|index| words[index / 8] >> (7 - index % 8) & 1 == 1).collect()
+        };
+        let bits = msb0(&WORDS);
+        for start in 0..=40 {
+            for end in start..=40 {
+                let slice = BitSlice::with_order(&WORDS, Msb0).slice(start..end).unwrap();
+                let expected: Vec<usize> = (0..end - start).filter(|&index| bits[start + index]).collect();
+                assert_eq!(slice.iter_ones().collect::<Vec<_>>(), expected);
+                assert_eq!(slice.count_ones(), expected.len());
+                let mut words = WORDS;
+                BitSliceMut::with_order(&mut words[..], Msb0).set_range(start..end).unwrap();
+                let mut expected = bits.clone();
+                for bit in &mut expected[start..end] {
+                    *bit = true;
+                }
+                assert_eq!(msb0(&words), expected);
+            }
+        }
+        let mut words = [0u16];
+        let mut view = BitSliceMut::with_order(&mut words[..], Msb0);
+        view.set_bit(0).unwrap();
+        assert_eq!(view.get_bit(0), Some(true));
+        assert_eq!(words, [1 << 15]);
+    }
+
+    #[test]
+    fn signed_words() {
+        let mut words = [0i8; 2];
+        BitSliceMut::new(&mut words).set_range(7..9).unwrap();
+        assert_eq!(words, [i8::MIN, 1]);
+        assert_eq!(BitSlice::new(&words).iter_ones().collect::<Vec<_>>(), [7, 8]);
+    }
+}
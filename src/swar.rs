@@ -0,0 +1,329 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! SIMD within a register: arithmetic on the lanes packed inside a single word.
+//!
+//! A `u32`, `u64` or `u128` is treated as lanes of 4, 8 or 16 bits, with lane `0` in the
+//! least significant bits. The operations never carry or borrow between lanes.
+
+use crate::Bitwise;
+
+/// Width of the lanes packed inside a word
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LaneWidth {
+    /// Lanes of 4 bits
+    Four,
+    /// Lanes of 8 bits
+    Eight,
+    /// Lanes of 16 bits
+    Sixteen,
+}
+
+impl LaneWidth {
+    /// Returns the number of bits of each lane
+    pub fn bits(self) -> usize {
+        match self {
+            LaneWidth::Four => 4,
+            LaneWidth::Eight => 8,
+            LaneWidth::Sixteen => 16,
+        }
+    }
+}
+
+/// Lane-wise operations on the words that hold several lanes
+pub trait Swar: Bitwise {
+    /// Returns a word with `value` repeated in every lane
+    ///
+    /// **Note:** The bits of `value` that do not fit in a lane are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::swar::{LaneWidth, Swar};
+    ///
+    /// assert_eq!(u32::splat(0xA, LaneWidth::Four), 0xAAAA_AAAA);
+    /// assert_eq!(u64::splat(0x1234, LaneWidth::Sixteen), 0x1234_1234_1234_1234);
+    /// ```
+    fn splat(value: Self, width: LaneWidth) -> Self;
+
+    /// Returns a word with `byte` repeated in every byte
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::swar::Swar;
+    ///
+    /// assert_eq!(u64::broadcast_byte(b','), 0x2C2C_2C2C_2C2C_2C2C);
+    /// ```
+    fn broadcast_byte(byte: u8) -> Self;
+
+    /// Adds each lane of `rhs` to the same lane of the word, wrapping inside the lane
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::swar::{LaneWidth, Swar};
+    ///
+    /// assert_eq!(0x01FF_0203u32.lanes_add(0x0101_0101, LaneWidth::Eight), 0x0200_0304);
+    /// ```
+    fn lanes_add(self, rhs: Self, width: LaneWidth) -> Self;
+
+    /// Subtracts each lane of `rhs` from the same lane of the word, wrapping inside the lane
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::swar::{LaneWidth, Swar};
+    ///
+    /// assert_eq!(0x0200_0304u32.lanes_sub(0x0101_0101, LaneWidth::Eight), 0x01FF_0203);
+    /// ```
+    fn lanes_sub(self, rhs: Self, width: LaneWidth) -> Self;
+
+    /// Compares the lanes for equality, turning on all the bits of the lanes that are equal
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::swar::{LaneWidth, Swar};
+    ///
+    /// assert_eq!(0x1234_5678u32.lanes_eq(0x1200_5600, LaneWidth::Eight), 0xFF00_FF00);
+    /// ```
+    fn lanes_eq(self, rhs: Self, width: LaneWidth) -> Self;
+
+    /// Compares the lanes as unsigned numbers, turning on all the bits of the lanes of the
+    /// word that are less than the same lane of `rhs`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::swar::{LaneWidth, Swar};
+    ///
+    /// assert_eq!(0x0180_FF05u32.lanes_lt(0x0280_0006, LaneWidth::Eight), 0xFF00_00FF);
+    /// ```
+    fn lanes_lt(self, rhs: Self, width: LaneWidth) -> Self;
+
+    /// Turns on all the bits of the lanes that are zero
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::swar::{LaneWidth, Swar};
+    ///
+    /// assert_eq!(0x1200_0080u32.lanes_zero(LaneWidth::Eight), 0x00FF_FF00);
+    /// ```
+    fn lanes_zero(self, width: LaneWidth) -> Self;
+
+    /// Returns `true` when at least one lane is zero
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::swar::{LaneWidth, Swar};
+    ///
+    /// assert!(0x1200_3456u32.has_zero_lane(LaneWidth::Eight));
+    /// assert!(!0x1200_3456u32.has_zero_lane(LaneWidth::Sixteen));
+    /// ```
+    fn has_zero_lane(self, width: LaneWidth) -> bool;
+
+    /// Returns the sum of all the lanes as unsigned numbers
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::swar::{LaneWidth, Swar};
+    ///
+    /// assert_eq!(0x0102_0304u32.lanes_sum(LaneWidth::Eight), 10);
+    /// assert_eq!(u128::MAX.lanes_sum(LaneWidth::Four), 32 * 15);
+    /// ```
+    fn lanes_sum(self, width: LaneWidth) -> u64;
+}
+
+/// The largest number that fits in a lane of `$bits` bits.
+macro_rules! lane_max {
+    ($t:ident, $bits:expr) => {
+        <$t>::get_range_mask_unchecked(..$bits)
+    };
+}
+
+/// A word with the least significant bit of every lane turned on.
+macro_rules! lanes_low {
+    ($t:ident, $bits:expr) => {
+        !(0 as $t) / lane_max!($t, $bits)
+    };
+}
+
+/// A word with the most significant bit of every lane turned on.
+macro_rules! lanes_high {
+    ($t:ident, $bits:expr) => {
+        lanes_low!($t, $bits) << ($bits - 1)
+    };
+}
+
+macro_rules! impl_swar {
+    ($($t:ident),*) => {$(
+        impl Swar for $t {
+            fn splat(value: Self, width: LaneWidth) -> Self {
+                (value & lane_max!($t, width.bits())) * lanes_low!($t, width.bits())
+            }
+
+            fn broadcast_byte(byte: u8) -> Self {
+                Self::splat(byte as Self, LaneWidth::Eight)
+            }
+
+            fn lanes_add(self, rhs: Self, width: LaneWidth) -> Self {
+                let high = lanes_high!($t, width.bits());
+                ((self & !high) + (rhs & !high)) ^ ((self ^ rhs) & high)
+            }
+
+            fn lanes_sub(self, rhs: Self, width: LaneWidth) -> Self {
+                let high = lanes_high!($t, width.bits());
+                ((self | high) - (rhs & !high)) ^ ((self ^ !rhs) & high)
+            }
+
+            fn lanes_eq(self, rhs: Self, width: LaneWidth) -> Self {
+                (self ^ rhs).lanes_zero(width)
+            }
+
+            fn lanes_lt(self, rhs: Self, width: LaneWidth) -> Self {
+                let bits = width.bits();
+                let difference = self.lanes_sub(rhs, width);
+                // The borrow out of each lane lands in its most significant bit.
+                let borrow = ((!self & rhs) | (!(self ^ rhs) & difference)) & lanes_high!($t, bits);
+                (borrow >> (bits - 1)) * lane_max!($t, bits)
+            }
+
+            fn lanes_zero(self, width: LaneWidth) -> Self {
+                let bits = width.bits();
+                let high = lanes_high!($t, bits);
+                // The most significant bit of each lane ends up on when the lane is not zero.
+                let nonzero = (((self & !high) + !high) | self) & high;
+                ((!nonzero & high) >> (bits - 1)) * lane_max!($t, bits)
+            }
+
+            fn has_zero_lane(self, width: LaneWidth) -> bool {
+                self.lanes_zero(width) != 0
+            }
+
+            fn lanes_sum(self, width: LaneWidth) -> u64 {
+                let mut lanes = self;
+                let mut bits = width.bits();
+                while bits < 64.min(Self::bit_size()) {
+                    let even = lane_max!($t, bits) * lanes_low!($t, 2 * bits);
+                    lanes = (lanes & even) + ((lanes >> bits) & even);
+                    bits *= 2;
+                }
+                let mut sum = 0;
+                let mut offset = 0;
+                while offset < Self::bit_size() {
+                    sum += (lanes >> offset) as u64;
+                    offset += 64;
+                }
+                sum
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "u32")]
+impl_swar!(u32);
+
+#[cfg(feature = "u64")]
+impl_swar!(u64);
+
+#[cfg(feature = "u128")]
+impl_swar!(u128);
+
+#[cfg(test)]
+mod tests {
+    use super::{LaneWidth, Swar};
+    use crate::Bitwise;
+
+    const WIDTHS: [LaneWidth; 3] = [LaneWidth::Four, LaneWidth::Eight, LaneWidth::Sixteen];
+
+    fn lanes(word: u128, bits: usize, count: usize) -> Vec<u128> {
+        (0..count).map(|lane| (word >> (lane * bits)) & ((1 << bits) - 1)).collect()
+    }
+
+    fn join(lanes: &[u128], bits: usize) -> u128 {
+        lanes.iter().enumerate().fold(0, |word, (lane, &value)| word | (value << (lane * bits)))
+    }
+
+    const SAMPLES: [u128; 8] = [
+        0,
+        u128::MAX,
+        0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210,
+        0x8000_8000_0000_FFFF_0001_7FFF_8080_0F0F,
+        0x00FF_00FF_1010_2020_F0F0_0000_0101_FFFF,
+        0x8888_8888_7777_7777_0000_0000_1111_1111,
+        0xDEAD_BEEF_CAFE_BABE_0BAD_F00D_FACE_B00C,
+        1,
+    ];
+
+    macro_rules! check_against_lanes {
+        ($($t:ident),*) => {$(
+            for &width in &WIDTHS {
+                let bits = width.bits();
+                let count = <$t as Bitwise>::bit_size() / bits;
+                let max = (1u128 << bits) - 1;
+                for &a in &SAMPLES {
+                    for &b in &SAMPLES {
+                        let (a, b) = (a as $t, b.rotate_left(13) as $t);
+                        let (la, lb) = (lanes(a as u128, bits, count), lanes(b as u128, bits, count));
+                        let add: Vec<u128> = la.iter().zip(&lb).map(|(x, y)| (x + y) & max).collect();
+                        assert_eq!(a.lanes_add(b, width) as u128, join(&add, bits));
+                        let sub: Vec<u128> = la.iter().zip(&lb).map(|(x, y)| x.wrapping_sub(*y) & max).collect();
+                        assert_eq!(a.lanes_sub(b, width) as u128, join(&sub, bits));
+                        let eq: Vec<u128> = la.iter().zip(&lb).map(|(x, y)| if x == y { max } else { 0 }).collect();
+                        assert_eq!(a.lanes_eq(b, width) as u128, join(&eq, bits));
+                        assert_eq!(a.lanes_eq(a, width), <$t>::MAX);
+                        let lt: Vec<u128> = la.iter().zip(&lb).map(|(x, y)| if x < y { max } else { 0 }).collect();
+                        assert_eq!(a.lanes_lt(b, width) as u128, join(&lt, bits));
+                        let zero: Vec<u128> = la.iter().map(|&x| if x == 0 { max } else { 0 }).collect();
+                        assert_eq!(a.lanes_zero(width) as u128, join(&zero, bits));
+                        assert_eq!(a.has_zero_lane(width), la.contains(&0));
+                        assert_eq!(a.lanes_sum(width) as u128, la.iter().sum::<u128>());
+                    }
+                }
+            }
+        )*};
+    }
+
+    #[test]
+    fn lanes_match_scalar_arithmetic() {
+        check_against_lanes!(u32, u64, u128);
+    }
+
+    #[test]
+    fn splat() {
+        assert_eq!(u32::splat(0xA, LaneWidth::Four), 0xAAAA_AAAA);
+        assert_eq!(u32::splat(0x1FF, LaneWidth::Eight), 0xFFFF_FFFF);
+        assert_eq!(u128::splat(0x1234, LaneWidth::Sixteen), 0x1234_1234_1234_1234_1234_1234_1234_1234);
+    }
+
+    #[test]
+    fn broadcast_byte() {
+        assert_eq!(u32::broadcast_byte(0x2C), 0x2C2C_2C2C);
+        assert_eq!(u64::broadcast_byte(0xFF), u64::MAX);
+        assert_eq!(u128::broadcast_byte(0), 0);
+    }
+
+    #[test]
+    fn has_zero_lane() {
+        assert!(0x1200_3456u32.has_zero_lane(LaneWidth::Eight));
+        assert!(!0x1200_3456u32.has_zero_lane(LaneWidth::Sixteen));
+        assert!(0x1230_3456u32.has_zero_lane(LaneWidth::Four));
+        assert!(!0x0101_0101_0101_0101u64.has_zero_lane(LaneWidth::Eight));
+        assert!(0x0100_0101_0101_0101u64.has_zero_lane(LaneWidth::Eight));
+    }
+
+    #[test]
+    fn lanes_sum() {
+        assert_eq!(0x0102_0304u32.lanes_sum(LaneWidth::Eight), 10);
+        assert_eq!(u32::MAX.lanes_sum(LaneWidth::Sixteen), 2 * 0xFFFF);
+        assert_eq!(u128::MAX.lanes_sum(LaneWidth::Four), 32 * 15);
+        assert_eq!(u128::MAX.lanes_sum(LaneWidth::Sixteen), 8 * 0xFFFF);
+    }
+}
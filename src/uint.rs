@@ -0,0 +1,616 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Unsigned integers of an arbitrary width between 1 and 128 bits.
+//!
+//! A [`UInt<BITS>`](UInt) is stored in the smallest unsigned primitive with at least `BITS`
+//! bits, as chosen by the [`Backing`] implementation of [`Width<BITS>`](Width), and every
+//! operation masks its result back to `BITS` bits.
+
+use std::fmt;
+use std::hash::Hash;
+use std::ops::RangeBounds;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Not, Shl, Shr, Sub};
+
+use crate::{range_to_bounds, Bitwise, UnsignedBitwise};
+
+/// A width in bits, only used to select the storage of a [`UInt`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Width<const BITS: usize>;
+
+/// The storage of the [`UInt`] of a width
+///
+/// It is implemented for every `Width<BITS>` with `BITS` between 1 and 128, as long as the
+/// feature of the selected primitive is enabled.
+pub trait Backing {
+    /// The smallest unsigned primitive with at least `BITS` bits
+    type Word: UnsignedBitwise + Ord + Hash + Default + fmt::Debug + fmt::Display;
+}
+
+macro_rules! impl_backing {
+    ($feature:literal, $word:ident => $($bits:literal)*) => {$(
+        #[cfg(feature = $feature)]
+        impl Backing for Width<$bits> {
+            type Word = $word;
+        }
+    )*};
+}
+
+impl_backing!("u8", u8 => 1 2 3 4 5 6 7 8);
+impl_backing!("u16", u16 => 9 10 11 12 13 14 15 16);
+impl_backing!("u32", u32 => 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32);
+impl_backing!("u64", u64 => 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56
+    57 58 59 60 61 62 63 64);
+impl_backing!("u128", u128 => 65 66 67 68 69 70 71 72 73 74 75 76 77 78 79 80 81 82 83 84 85 86 87
+    88 89 90 91 92 93 94 95 96 97 98 99 100 101 102 103 104 105 106 107 108 109 110 111 112 113 114
+    115 116 117 118 119 120 121 122 123 124 125 126 127 128);
+
+type Word<const BITS: usize> = <Width<BITS> as Backing>::Word;
+
+/// An unsigned integer of `BITS` bits
+///
+/// **Note:** The bits above `BITS` of the storage are always zero. The arithmetic operators
+/// wrap around modulo `2^BITS`, the shifts are logical and the order is the numeric one.
+/// Shifting by `BITS` or more bits and dividing by zero panic.
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::{Bitwise, UInt};
+///
+/// let value = UInt::<12>::new(0xFFF).unwrap();
+/// assert_eq!((value + UInt::one()).value(), 0);
+/// assert_eq!(value.checked_add(UInt::one()), None);
+/// assert_eq!((!UInt::<12>::zero()).value(), 0xFFF);
+/// assert_eq!(UInt::<12>::bit_size(), 12);
+/// assert_eq!(std::mem::size_of::<UInt<12>>(), 2);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UInt<const BITS: usize>
+where
+    Width<BITS>: Backing,
+{
+    value: Word<BITS>,
+}
+
+impl<const BITS: usize> UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    /// Creates an integer from `value`, or returns `None` if it does not fit in `BITS` bits
+    pub fn new(value: Word<BITS>) -> Option<Self> {
+        if value > Self::max_value().value {
+            return None;
+        }
+        Some(UInt { value })
+    }
+
+    /// Creates an integer from the `BITS` least significant bits of `value`
+    pub fn new_truncated(value: Word<BITS>) -> Self {
+        UInt { value: value & Self::max_value().value }
+    }
+
+    /// Returns the value of the integer
+    pub fn value(self) -> Word<BITS> {
+        self.value
+    }
+
+    /// Returns the smallest value of the integer, zero
+    pub fn min_value() -> Self {
+        Self::zero()
+    }
+
+    /// Returns the largest value of the integer, `2^BITS - 1`
+    pub fn max_value() -> Self {
+        UInt { value: Word::<BITS>::get_range_mask_unchecked(..BITS) }
+    }
+
+    /// Returns the sum of the integers, or `None` if it does not fit in `BITS` bits
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let (sum, carry) = UnsignedBitwise::carrying_add(self.value, rhs.value, false);
+        if carry {
+            return None;
+        }
+        Self::new(sum)
+    }
+
+    /// Returns the difference of the integers, or `None` if it is negative
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let (difference, borrow) = UnsignedBitwise::borrowing_sub(self.value, rhs.value, false);
+        if borrow {
+            return None;
+        }
+        Some(UInt { value: difference })
+    }
+
+    /// Returns the product of the integers, or `None` if it does not fit in `BITS` bits
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let (lo, hi) = UnsignedBitwise::widening_mul(self.value, rhs.value);
+        if hi != Word::<BITS>::zero() {
+            return None;
+        }
+        Self::new(lo)
+    }
+
+    /// Returns the quotient of the integers, or `None` if `rhs` is zero
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs == Self::zero() {
+            return None;
+        }
+        Some(self / rhs)
+    }
+
+    /// Returns the sum of the integers modulo `2^BITS`
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        Self::new_truncated(UnsignedBitwise::carrying_add(self.value, rhs.value, false).0)
+    }
+
+    /// Returns the difference of the integers modulo `2^BITS`
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        Self::new_truncated(UnsignedBitwise::borrowing_sub(self.value, rhs.value, false).0)
+    }
+
+    /// Returns the product of the integers modulo `2^BITS`
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        Self::new_truncated(UnsignedBitwise::widening_mul(self.value, rhs.value).0)
+    }
+
+    /// Returns the sum of the integers, or `max_value()` if it does not fit in `BITS` bits
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or_else(Self::max_value)
+    }
+
+    /// Returns the difference of the integers, or zero if it is negative
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or_else(Self::min_value)
+    }
+
+    /// Returns the product of the integers, or `max_value()` if it does not fit in `BITS` bits
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).unwrap_or_else(Self::max_value)
+    }
+
+    /// Reads the integer from the field of `BITS` bits starting at `offset` of a packed word,
+    /// like `extract_field_unchecked` does
+    pub fn from_field_unchecked<T: Bitwise>(word: T, offset: usize) -> Self {
+        Self::new_truncated(word.extract_field_unchecked(offset, BITS).truncate_to())
+    }
+
+    /// Reads the integer from the field of `BITS` bits starting at `offset` of a packed word,
+    /// or returns `None` if the field does not fit in the word
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::UInt;
+    ///
+    /// let packed: u32 = 0x00AB_C123;
+    /// assert_eq!(UInt::<12>::from_field(packed, 12).unwrap().value(), 0xABC);
+    /// assert_eq!(UInt::<12>::from_field(packed, 24), None);
+    /// ```
+    pub fn from_field<T: Bitwise>(word: T, offset: usize) -> Option<Self> {
+        check_field_or_return_none!(offset, BITS, T::bit_size());
+        Some(Self::from_field_unchecked(word, offset))
+    }
+
+    /// Writes the integer into the field of `BITS` bits starting at `offset` of a packed word,
+    /// like `insert_field_unchecked` does
+    pub fn insert_into_unchecked<T: Bitwise>(self, word: T, offset: usize) -> T {
+        let width = BITS.min(T::bit_size());
+        word.insert_field_unchecked(offset, width, self.value.truncate_to())
+    }
+
+    /// Writes the integer into the field of `BITS` bits starting at `offset` of a packed word,
+    /// or returns `None` if the field does not fit in the word
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::UInt;
+    ///
+    /// let field = UInt::<3>::new(0b101).unwrap();
+    /// assert_eq!(field.insert_into(0xFFu8, 2), Some(0b1111_0111));
+    /// assert_eq!(field.insert_into(0xFFu8, 6), None);
+    /// ```
+    pub fn insert_into<T: Bitwise>(self, word: T, offset: usize) -> Option<T> {
+        check_field_or_return_none!(offset, BITS, T::bit_size());
+        Some(self.insert_into_unchecked(word, offset))
+    }
+
+    fn count_ones(self) -> usize {
+        self.value.count_ones_in_range_unchecked(..)
+    }
+
+    fn trailing_zeros(self) -> usize {
+        self.value.count_trailing_zeros().min(BITS)
+    }
+
+    fn leading_zeros(self) -> usize {
+        self.value.count_leading_zeros() - (Word::<BITS>::bit_size() - BITS)
+    }
+
+    fn reverse_bits(self) -> Self {
+        UInt { value: self.value.reverse_low_bits_unchecked(BITS) }
+    }
+
+    fn shift_left(self, shift: usize) -> Self {
+        if shift >= BITS {
+            return Self::zero();
+        }
+        Self::new_truncated(self.value << shift as u32)
+    }
+
+    fn shift_right(self, shift: usize) -> Self {
+        if shift >= BITS {
+            return Self::zero();
+        }
+        UInt { value: self.value >> shift as u32 }
+    }
+}
+
+impl<const BITS: usize> fmt::Display for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+impl<const BITS: usize> Not for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::new_truncated(!self.value)
+    }
+}
+
+impl<const BITS: usize> BitAnd for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        UInt { value: self.value & rhs.value }
+    }
+}
+
+impl<const BITS: usize> BitOr for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        UInt { value: self.value | rhs.value }
+    }
+}
+
+impl<const BITS: usize> BitXor for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        UInt { value: self.value ^ rhs.value }
+    }
+}
+
+impl<const BITS: usize> Shl<u32> for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn shl(self, rhs: u32) -> Self {
+        assert!((rhs as usize) < BITS, "attempt to shift left with overflow");
+        self.shift_left(rhs as usize)
+    }
+}
+
+impl<const BITS: usize> Shr<u32> for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn shr(self, rhs: u32) -> Self {
+        assert!((rhs as usize) < BITS, "attempt to shift right with overflow");
+        self.shift_right(rhs as usize)
+    }
+}
+
+impl<const BITS: usize> Add for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.wrapping_add(rhs)
+    }
+}
+
+impl<const BITS: usize> Sub for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.wrapping_sub(rhs)
+    }
+}
+
+impl<const BITS: usize> Mul for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.wrapping_mul(rhs)
+    }
+}
+
+impl<const BITS: usize> Div for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        assert!(rhs != Self::zero(), "attempt to divide by zero");
+        UInt { value: self.value / rhs.value }
+    }
+}
+
+impl<const BITS: usize> Bitwise for UInt<BITS>
+where
+    Width<BITS>: Backing,
+{
+    fn get_bit_unchecked(self, index: usize) -> bool {
+        self.value.get_bit_unchecked(index)
+    }
+
+    fn set_bit_unchecked(self, index: usize) -> Self {
+        Self::new_truncated(self.value.set_bit_unchecked(index))
+    }
+
+    fn get_range_mask_unchecked<R: RangeBounds<usize>>(range: R) -> Self {
+        let (start, end) = range_to_bounds(&range, BITS);
+        let end = end.min(BITS);
+        if start >= end {
+            return Self::zero();
+        }
+        UInt { value: Word::<BITS>::get_range_mask_unchecked(start..end) }
+    }
+
+    fn count_ones_in_range_unchecked<R: RangeBounds<usize>>(self, range: R) -> usize {
+        (self & Self::get_range_mask_unchecked(range)).count_ones()
+    }
+
+    fn extract_field_unchecked(self, offset: usize, width: usize) -> Self {
+        if width == 0 {
+            return Self::zero();
+        }
+        self.shift_right(offset) & Self::get_range_mask_unchecked(..width)
+    }
+
+    fn clear_bit_unchecked(self, index: usize) -> Self {
+        UInt { value: self.value.clear_bit_unchecked(index) }
+    }
+
+    fn flip_bit_unchecked(self, index: usize) -> Self {
+        Self::new_truncated(self.value.flip_bit_unchecked(index))
+    }
+
+    fn count_trailing_zeros(self) -> usize {
+        self.trailing_zeros()
+    }
+
+    fn count_leading_zeros(self) -> usize {
+        self.leading_zeros()
+    }
+
+    fn reverse_low_bits_unchecked(self, n: usize) -> Self {
+        if n == 0 {
+            return Self::zero();
+        }
+        self.reverse_bits().extract_field_unchecked(BITS - n, n)
+    }
+
+    fn zero() -> Self {
+        UInt { value: Word::<BITS>::zero() }
+    }
+
+    fn one() -> Self {
+        UInt { value: Word::<BITS>::one() }
+    }
+
+    fn bit_size() -> usize {
+        BITS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use super::UInt;
+    use crate::order::SetOrder;
+    use crate::Bitwise;
+
+    fn uint12(value: u16) -> UInt<12> {
+        UInt::new(value).unwrap()
+    }
+
+    const SAMPLES: [u16; 8] = [0, 1, 0xFFF, 0x800, 0x7FF, 0xABC, 0x00F, 0x5A5];
+
+    #[test]
+    fn storage_is_the_smallest_primitive() {
+        assert_eq!(size_of::<UInt<1>>(), 1);
+        assert_eq!(size_of::<UInt<3>>(), 1);
+        assert_eq!(size_of::<UInt<12>>(), 2);
+        assert_eq!(size_of::<UInt<32>>(), 4);
+        assert_eq!(size_of::<UInt<48>>(), 8);
+        assert_eq!(size_of::<UInt<100>>(), 16);
+        assert_eq!(UInt::<48>::bit_size(), 48);
+    }
+
+    #[test]
+    fn new_and_limits() {
+        assert_eq!(UInt::<3>::new(7).map(UInt::value), Some(7));
+        assert_eq!(UInt::<3>::new(8), None);
+        assert_eq!(UInt::<3>::new_truncated(0b1110).value(), 0b110);
+        assert_eq!(UInt::<3>::max_value().value(), 7);
+        assert_eq!(UInt::<64>::max_value().value(), u64::MAX);
+        assert_eq!(UInt::<128>::max_value().value(), u128::MAX);
+        assert_eq!(UInt::<48>::min_value().value(), 0);
+        assert_eq!(uint12(0xABC).to_string(), "2748");
+    }
+
+    #[test]
+    fn operators_match_masked_u16() {
+        for &a in &SAMPLES {
+            assert_eq!((!uint12(a)).value(), !a & 0xFFF);
+            for shift in 0..12 {
+                assert_eq!((uint12(a) << shift).value(), (a << shift) & 0xFFF);
+                assert_eq!((uint12(a) >> shift).value(), a >> shift);
+            }
+            for &b in &SAMPLES {
+                assert_eq!((uint12(a) & uint12(b)).value(), a & b);
+                assert_eq!((uint12(a) | uint12(b)).value(), a | b);
+                assert_eq!((uint12(a) ^ uint12(b)).value(), a ^ b);
+                assert_eq!((uint12(a) + uint12(b)).value(), a.wrapping_add(b) & 0xFFF);
+                assert_eq!((uint12(a) - uint12(b)).value(), a.wrapping_sub(b) & 0xFFF);
+                assert_eq!((uint12(a) * uint12(b)).value(), a.wrapping_mul(b) & 0xFFF);
+                if b != 0 {
+                    assert_eq!((uint12(a) / uint12(b)).value(), a / b);
+                }
+                assert_eq!(uint12(a).cmp(&uint12(b)), a.cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn checked_wrapping_and_saturating() {
+        for a in 0..8u8 {
+            for b in 0..8u8 {
+                let (x, y) = (UInt::<3>::new(a).unwrap(), UInt::<3>::new(b).unwrap());
+                let expected = |value: u8| if value < 8 { UInt::new(value) } else { None };
+                assert_eq!(x.checked_add(y), expected(a + b));
+                assert_eq!(x.checked_sub(y), a.checked_sub(b).and_then(expected));
+                assert_eq!(x.checked_mul(y), expected(a * b));
+                assert_eq!(x.checked_div(y), a.checked_div(b).and_then(expected));
+                assert_eq!(x.wrapping_add(y).value(), (a + b) % 8);
+                assert_eq!(x.wrapping_sub(y).value(), a.wrapping_sub(b) % 8);
+                assert_eq!(x.wrapping_mul(y).value(), (a * b) % 8);
+                assert_eq!(x.saturating_add(y).value(), (a + b).min(7));
+                assert_eq!(x.saturating_sub(y).value(), a.saturating_sub(b));
+                assert_eq!(x.saturating_mul(y).value(), (a * b).min(7));
+            }
+        }
+        let max = UInt::<64>::max_value();
+        assert_eq!(max.checked_add(UInt::one()), None);
+        assert_eq!(max.checked_mul(max), None);
+        assert_eq!((max + UInt::one()).value(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to shift left with overflow")]
+    fn shift_overflow() {
+        let _ = UInt::<12>::one() << 12;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn divide_by_zero() {
+        let _ = UInt::<12>::one() / UInt::zero();
+    }
+
+    #[test]
+    fn bits_stay_within_the_width() {
+        let value = uint12(0x0F0);
+        assert_eq!(value.flip().value(), 0xF0F);
+        assert_eq!(value.set_all().value(), 0xFFF);
+        assert_eq!(value.get_bit(12), None);
+        assert_eq!(value.set_bit(12), None);
+        assert_eq!(value.set_bit_unchecked(12), value);
+        assert_eq!(value.set_range(4..13), None);
+        assert_eq!(value.set_range_unchecked(8..).value(), 0xFF0);
+        assert_eq!(UInt::<12>::get_range_mask_unchecked(..).value(), 0xFFF);
+        assert_eq!(value.count_ones_in_range_unchecked(..), 4);
+        assert_eq!(value.count_leading_zeros(), 4);
+        assert_eq!(UInt::<12>::zero().count_trailing_zeros(), 12);
+        assert_eq!(value.highest_clear_bit(), Some(11));
+        assert_eq!(value.next_clear_bit(8), Some(8));
+        assert_eq!(value.iter_zeros().count(), 8);
+        assert_eq!(value.select(3), Some(7));
+        assert_eq!(value.hamming_distance(uint12(0xFFF)), 8);
+    }
+
+    #[test]
+    fn methods_match_the_width() {
+        let value = uint12(0b0000_0110_1001);
+        assert_eq!(value.reverse_low_bits_unchecked(12).value(), 0b1001_0110_0000);
+        assert_eq!(value.reverse_bits_in_range_unchecked(..).value(), 0b1001_0110_0000);
+        assert_eq!(value.rotate_range_left_unchecked(.., 4).value(), 0b0110_1001_0000);
+        assert_eq!(value.rotate_range_right_unchecked(.., 4).value(), 0b1001_0000_0110);
+        assert_eq!(Bitwise::funnel_shl(value, uint12(0xF00), 4).value(), 0b0110_1001_1111);
+        assert_eq!(Bitwise::funnel_shr(uint12(0xF00), value, 4).value(), 0b0000_0000_0110);
+        assert_eq!(uint12(0x800).sign_extend_to::<i16>(), -2048);
+        assert_eq!(uint12(0x800).truncate_to::<u8>(), 0);
+        assert_eq!(uint12(0x0F8).sign_extend_from_unchecked(8).value(), 0xFF8);
+        assert_eq!(uint12(0xFFF).next_power_of_two_checked(), None);
+        assert_eq!(uint12(0x7FF).next_power_of_two_checked(), Some(uint12(0x800)));
+        assert_eq!(Bitwise::isolate_lowest_one(value).value(), 1);
+        assert_eq!(uint12(0xFFF).lowest_zero_mask().value(), 0);
+        assert_eq!(uint12(0x800).next_same_popcount(), None);
+        assert_eq!(UInt::<3>::combinations(3, 2).count(), 3);
+        for a in 0..16u16 {
+            let gray = uint12(a).to_gray();
+            assert_eq!(gray.value(), a ^ (a >> 1));
+            assert_eq!(gray.from_gray(), uint12(a));
+        }
+    }
+
+    #[test]
+    fn orders_cover_the_width() {
+        let mut value = UInt::<4>::zero();
+        let mut count = 1;
+        while let Some(next) = value.next_in_order(SetOrder::Colex) {
+            value = next;
+            count += 1;
+        }
+        assert_eq!(count, 16);
+        assert_eq!(value, UInt::max_value());
+    }
+
+    #[test]
+    fn packed_fields() {
+        let packed: u64 = 0x0000_1234_5678_9ABC;
+        let field = UInt::<48>::from_field(packed, 0).unwrap();
+        assert_eq!(field.value(), 0x1234_5678_9ABC);
+        assert_eq!(UInt::<48>::from_field(packed, 17), None);
+        assert_eq!(UInt::<12>::from_field(packed, 12).map(UInt::value), Some(0x789));
+        assert_eq!(UInt::<12>::from_field(-1i16, 4).map(UInt::value), Some(0xFFF));
+        assert_eq!(uint12(0xABC).insert_into(0u64, 52), Some(0xABC0_0000_0000_0000));
+        assert_eq!(uint12(0xABC).insert_into(0u64, 53), None);
+        assert_eq!(uint12(0x800).insert_into(0i16, 4), Some(i16::MIN));
+        let packed = (0..5).fold(0u16, |packed, index| {
+            UInt::<3>::new(index).unwrap().insert_into(packed, 3 * index as usize).unwrap()
+        });
+        for index in 0..5 {
+            assert_eq!(UInt::<3>::from_field(packed, 3 * index as usize).unwrap().value(), index);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Multi-word arithmetic primitives for the unsigned integers.
+
+use crate::Bitwise;
+
+/// Arithmetic primitives to build multi-word integers out of unsigned words
+///
+/// The standard library may add inherent methods with some of these names, so prefer calling
+/// them through the trait.
+pub trait UnsignedBitwise: Bitwise {
+    /// Returns the sum of the number, `rhs` and `carry`, together with the carry out
+    ///
+    /// **Possible values:** `(integer, bool)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::UnsignedBitwise;
+    ///
+    /// assert_eq!(UnsignedBitwise::carrying_add(200u8, 55, true), (0, true));
+    /// assert_eq!(UnsignedBitwise::carrying_add(200u8, 54, true), (255, false));
+    /// ```
+    fn carrying_add(self, rhs: Self, carry: bool) -> (Self, bool);
+
+    /// Returns the difference of the number minus `rhs` and `borrow`, together with the borrow out
+    ///
+    /// **Possible values:** `(integer, bool)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::UnsignedBitwise;
+    ///
+    /// assert_eq!(UnsignedBitwise::borrowing_sub(5u8, 5, true), (255, true));
+    /// assert_eq!(UnsignedBitwise::borrowing_sub(5u8, 4, true), (0, false));
+    /// ```
+    fn borrowing_sub(self, rhs: Self, borrow: bool) -> (Self, bool);
+
+    /// Returns the full product of the number and `rhs` as its `(lo, hi)` halves
+    ///
+    /// **Possible values:** `(integer, integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::UnsignedBitwise;
+    ///
+    /// assert_eq!(UnsignedBitwise::widening_mul(200u8, 3), (88, 2));
+    /// assert_eq!(UnsignedBitwise::widening_mul(u128::MAX, 2), (u128::MAX - 1, 1));
+    /// ```
+    fn widening_mul(self, rhs: Self) -> (Self, Self);
+
+    /// Splits the number into its most and least significant halves, as `(hi, lo)`
+    ///
+    /// **Possible values:** `(integer, integer)`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::UnsignedBitwise;
+    ///
+    /// assert_eq!(0xABCDu16.split_halves(), (0xAB, 0xCD));
+    /// ```
+    fn split_halves(self) -> (Self, Self);
+
+    /// Joins the least significant halves of `hi` and `lo` into a number. This is the inverse
+    /// of `split_halves`.
+    ///
+    /// **Note:** The most significant halves of `hi` and `lo` are ignored.
+    ///
+    /// **Possible values:** `integer`
+    ///
+    /// **Stable:** Yes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use xbitwise::UnsignedBitwise;
+    ///
+    /// assert_eq!(u16::join_halves(0xAB, 0xCD), 0xABCD);
+    /// ```
+    fn join_halves(hi: Self, lo: Self) -> Self;
+}
+
+/// Schoolbook multiplication of the halves, for the words without a wider primitive.
+fn widening_mul_halves<T: UnsignedBitwise>(lhs: T, rhs: T) -> (T, T) {
+    let (lhs_hi, lhs_lo) = lhs.split_halves();
+    let (rhs_hi, rhs_lo) = rhs.split_halves();
+
+    let low = lhs_lo * rhs_lo;
+    let (cross_hi, cross_lo) = (lhs_hi * rhs_lo).split_halves();
+    let (other_hi, other_lo) = (lhs_lo * rhs_hi).split_halves();
+    let high = lhs_hi * rhs_hi;
+
+    let (lo, carry) = low.carrying_add(T::join_halves(cross_lo, T::zero()), false);
+    let (lo, other_carry) = lo.carrying_add(T::join_halves(other_lo, T::zero()), false);
+    let (hi, _) = high.carrying_add(cross_hi, carry);
+    let (hi, _) = hi.carrying_add(other_hi, other_carry);
+    (lo, hi)
+}
+
+macro_rules! impl_unsigned_bitwise {
+    ($($t:ident => $wide:ident),*) => {$(
+        impl UnsignedBitwise for $t {
+            fn carrying_add(self, rhs: Self, carry: bool) -> (Self, bool) {
+                let (sum, first) = self.overflowing_add(rhs);
+                let (sum, second) = sum.overflowing_add(carry as Self);
+                (sum, first || second)
+            }
+
+            fn borrowing_sub(self, rhs: Self, borrow: bool) -> (Self, bool) {
+                let (difference, first) = self.overflowing_sub(rhs);
+                let (difference, second) = difference.overflowing_sub(borrow as Self);
+                (difference, first || second)
+            }
+
+            fn widening_mul(self, rhs: Self) -> (Self, Self) {
+                impl_unsigned_bitwise!(@widening_mul $wide, self, rhs)
+            }
+
+            fn split_halves(self) -> (Self, Self) {
+                let half = Self::bit_size() / 2;
+                (self >> half, self & Self::get_range_mask_unchecked(..half))
+            }
+
+            fn join_halves(hi: Self, lo: Self) -> Self {
+                let half = Self::bit_size() / 2;
+                (hi << half) | (lo & Self::get_range_mask_unchecked(..half))
+            }
+        }
+    )*};
+    (@widening_mul halves, $lhs:expr, $rhs:expr) => {
+        widening_mul_halves($lhs, $rhs)
+    };
+    (@widening_mul $wide:ident, $lhs:expr, $rhs:expr) => {{
+        let product = ($lhs as $wide) * ($rhs as $wide);
+        (product as Self, (product >> Self::bit_size()) as Self)
+    }};
+}
+
+#[cfg(feature = "u8")]
+impl_unsigned_bitwise!(u8 => u16);
+
+#[cfg(feature = "u16")]
+impl_unsigned_bitwise!(u16 => u32);
+
+#[cfg(feature = "u32")]
+impl_unsigned_bitwise!(u32 => u64);
+
+#[cfg(feature = "u64")]
+impl_unsigned_bitwise!(u64 => u128);
+
+#[cfg(feature = "u128")]
+impl_unsigned_bitwise!(u128 => halves);
+
+#[cfg(test)]
+mod tests {
+    use super::UnsignedBitwise;
+
+    #[test]
+    fn carrying_add() {
+        assert_eq!(UnsignedBitwise::carrying_add(200u8, 55, true), (0, true));
+        assert_eq!(UnsignedBitwise::carrying_add(200u8, 54, true), (255, false));
+        assert_eq!(UnsignedBitwise::carrying_add(u128::MAX, u128::MAX, true), (u128::MAX, true));
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                for &carry in &[false, true] {
+                    let sum = a as u16 + b as u16 + carry as u16;
+                    assert_eq!(UnsignedBitwise::carrying_add(a, b, carry), (sum as u8, sum > 255));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn borrowing_sub() {
+        assert_eq!(UnsignedBitwise::borrowing_sub(5u8, 5, true), (255, true));
+        assert_eq!(UnsignedBitwise::borrowing_sub(5u8, 4, true), (0, false));
+        assert_eq!(UnsignedBitwise::borrowing_sub(0u64, u64::MAX, true), (0, true));
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                for &borrow in &[false, true] {
+                    let difference = a as i16 - b as i16 - borrow as i16;
+                    assert_eq!(UnsignedBitwise::borrowing_sub(a, b, borrow), (difference as u8, difference < 0));
+                }
+            }
+        }
+    }
+
+    /// Reference 128-bit widening multiplication by shifting and adding.
+    fn shift_add_mul(lhs: u128, rhs: u128) -> (u128, u128) {
+        let (mut lo, mut hi) = (0u128, 0u128);
+        for bit in 0..128 {
+            if rhs >> bit & 1 == 1 {
+                let (sum, carry) = lo.overflowing_add(lhs << bit);
+                lo = sum;
+                hi += if bit == 0 { 0 } else { lhs >> (128 - bit) } + carry as u128;
+            }
+        }
+        (lo, hi)
+    }
+
+    #[test]
+    fn widening_mul() {
+        assert_eq!(UnsignedBitwise::widening_mul(200u8, 3), (88, 2));
+        assert_eq!(UnsignedBitwise::widening_mul(u64::MAX, u64::MAX), (1, u64::MAX - 1));
+        assert_eq!(UnsignedBitwise::widening_mul(u128::MAX, u128::MAX), (1, u128::MAX - 1));
+        assert_eq!(UnsignedBitwise::widening_mul(1u128 << 64, 1u128 << 64), (0, 1));
+        let samples = [0u64, 1, 2, 3, 0xFFFF_FFFF, 0x1_0000_0000, 0xDEAD_BEEF_CAFE_BABE, u64::MAX];
+        for &a in &samples {
+            for &b in &samples {
+                let (lo, hi) = UnsignedBitwise::widening_mul(a as u128, b as u128);
+                assert_eq!((lo, hi), (a as u128 * b as u128, 0));
+                let (lo, hi) = super::widening_mul_halves(a, b);
+                assert_eq!((lo, hi), UnsignedBitwise::widening_mul(a, b));
+                let wide = (a as u128) << 64 | b as u128;
+                let other = (b as u128) << 64 | a.rotate_left(7) as u128;
+                assert_eq!(UnsignedBitwise::widening_mul(wide, other), shift_add_mul(wide, other));
+            }
+        }
+    }
+
+    #[test]
+    fn split_halves() {
+        assert_eq!(0xABu8.split_halves(), (0xA, 0xB));
+        assert_eq!(0xABCDu16.split_halves(), (0xAB, 0xCD));
+        assert_eq!(u128::MAX.split_halves(), (u64::MAX as u128, u64::MAX as u128));
+    }
+
+    #[test]
+    fn join_halves() {
+        assert_eq!(u8::join_halves(0xA, 0xB), 0xAB);
+        assert_eq!(u16::join_halves(0xAB, 0xCD), 0xABCD);
+        assert_eq!(u16::join_halves(0xFFAB, 0xFFCD), 0xABCD);
+        for value in 0..=u16::MAX {
+            let (hi, lo) = value.split_halves();
+            assert_eq!(u16::join_halves(hi, lo), value);
+        }
+    }
+}
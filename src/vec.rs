@@ -0,0 +1,542 @@
+// Copyright 2022 Developers of the xbitwise project.
+//
+// Licensed under the MIT license <LICENSE or https://opensource.org/licenses/MIT>
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Growable vectors of bits stored in words of any `Bitwise` type.
+
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+use crate::bitorder::{self, BitOrder, ByteOrder, Lsb0};
+use crate::Bitwise;
+
+/// A growable vector of bits packed into words of type `W`, starting at the first word
+/// and at the bit of each word chosen by the bit order `O`
+///
+/// **Note:** The unused bits of the last word are always turned off, so the bulk
+/// operations can work a whole word at a time.
+///
+/// # Examples
+///
+/// ```rust
+/// use xbitwise::BitVec;
+///
+/// let mut bits: BitVec<u8> = BitVec::new();
+/// bits.extend_from_slice(&[true, false, true, true]);
+/// bits.insert(1, true);
+/// assert_eq!(bits.len(), 5);
+/// assert_eq!(bits.get(1), Some(true));
+/// assert_eq!(bits.count_ones(), 4);
+/// assert_eq!(bits.pop(), Some(true));
+/// ```
+///
+/// With the [`Msb0`](crate::bitorder::Msb0) order the first bit is the most significant
+/// bit of each word, as in network protocols:
+///
+/// ```rust
+/// use xbitwise::bitorder::{BigEndian, Msb0};
+/// use xbitwise::BitVec;
+///
+/// let mut bits = BitVec::<u16, _>::with_order(Msb0);
+/// bits.extend_from_slice(&[true, false, true]);
+/// assert_eq!(bits.as_words(), &[0b1010_0000_0000_0000]);
+/// assert_eq!(bits.to_bytes::<BigEndian>(), [0b1010_0000, 0]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitVec<W, O = Lsb0> {
+    words: Vec<W>,
+    len: usize,
+    order: PhantomData<O>,
+}
+
+impl<W: Bitwise> BitVec<W> {
+    /// Creates an empty vector
+    pub fn new() -> Self {
+        BitVec::with_order(Lsb0)
+    }
+}
+
+impl<W: Bitwise, O: BitOrder> BitVec<W, O> {
+    /// Creates an empty vector that places the bits in each word in the given order
+    ///
+    /// The order type is inferred from the argument, so only the word type has to be named, as in
+    /// `BitVec::<u16, _>::with_order(Msb0)`.
+    pub fn with_order(_order: O) -> Self {
+        BitVec { words: Vec::new(), len: 0, order: PhantomData }
+    }
+
+    /// Creates an empty vector with room for at least `bits` bits
+    pub fn with_capacity(bits: usize) -> Self {
+        BitVec { words: Vec::with_capacity(Self::words_for(bits)), len: 0, order: PhantomData }
+    }
+
+    /// Returns the number of bits in the vector
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` when the vector holds no bits
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the words holding the bits, with the unused bits of the last word turned off
+    pub fn as_words(&self) -> &[W] {
+        &self.words
+    }
+
+    /// Returns the bit in the index position
+    ///
+    /// **Note:** Returns `None` when `index` is not less than `len()`.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len {
+            return None;
+        }
+        let (word, bit) = Self::locate(index);
+        Some(self.word(word).get_bit_unchecked(bit))
+    }
+
+    /// Sets the bit in the index position to `value`
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index` is not less than `len()`.
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+        let (word, bit) = Self::locate(index);
+        self.set_word(word, self.word(word).update_bit_unchecked(bit, value));
+    }
+
+    /// Appends a bit to the end of the vector
+    pub fn push(&mut self, value: bool) {
+        let (word, bit) = Self::locate(self.len);
+        if word == self.words.len() {
+            self.words.push(W::zero());
+        }
+        self.set_word(word, self.word(word).update_bit_unchecked(bit, value));
+        self.len += 1;
+    }
+
+    /// Removes the last bit of the vector and returns it
+    ///
+    /// **Note:** Returns `None` when the vector is empty.
+    pub fn pop(&mut self) -> Option<bool> {
+        let value = self.get(self.len.checked_sub(1)?)?;
+        self.truncate(self.len - 1);
+        Some(value)
+    }
+
+    /// Inserts a bit in the index position, moving all the bits after it one position up
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index` is greater than `len()`.
+    pub fn insert(&mut self, index: usize, value: bool) {
+        assert!(index <= self.len, "insertion index (is {}) should be <= len (is {})", index, self.len);
+        self.push(false);
+        let (first, bit) = Self::locate(index);
+        for word in (first + 1..self.words.len()).rev() {
+            self.set_word(word, W::funnel_shl(self.word(word), self.word(word - 1), 1));
+        }
+        let current = self.word(first);
+        let moved = W::funnel_shl(current, W::zero(), 1).clear_range_unchecked(..=bit);
+        self.set_word(first, (current.clear_range_unchecked(bit..) | moved).update_bit_unchecked(bit, value));
+    }
+
+    /// Removes the bit in the index position and returns it, moving all the bits after it
+    /// one position down
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index` is not less than `len()`.
+    pub fn remove(&mut self, index: usize) -> bool {
+        assert!(index < self.len, "removal index (is {}) should be < len (is {})", index, self.len);
+        let (first, bit) = Self::locate(index);
+        let value = self.word(first).get_bit_unchecked(bit);
+        for word in first..self.words.len() {
+            let next = if word + 1 < self.words.len() { self.word(word + 1) } else { W::zero() };
+            let moved = W::funnel_shr(next, self.word(word), 1);
+            let result = if word == first {
+                self.word(word).clear_range_unchecked(bit..) | moved.clear_range_unchecked(..bit)
+            } else {
+                moved
+            };
+            self.set_word(word, result);
+        }
+        self.truncate(self.len - 1);
+        value
+    }
+
+    /// Shortens the vector to its first `len` bits
+    ///
+    /// **Note:** Does nothing when `len` is not less than `len()`.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        self.len = len;
+        self.words.truncate(Self::words_for(len));
+        self.clear_unused_bits();
+    }
+
+    /// Resizes the vector to `len` bits, filling the new bits with `value`
+    pub fn resize(&mut self, len: usize, value: bool) {
+        if len <= self.len {
+            self.truncate(len);
+            return;
+        }
+        let (word, bit) = Self::locate(self.len);
+        if bit != 0 {
+            self.set_word(word, self.word(word).update_range_unchecked(bit.., value));
+        }
+        let fill = if value { W::zero().set_all() } else { W::zero() };
+        self.words.resize(Self::words_for(len), fill);
+        self.len = len;
+        self.clear_unused_bits();
+    }
+
+    /// Appends all the bits of `values` to the end of the vector
+    pub fn extend_from_slice(&mut self, values: &[bool]) {
+        self.extend(values.iter().copied());
+    }
+
+    /// Splits the vector in two at the index position, returning the bits from `at` onwards
+    /// and keeping the first `at` bits
+    ///
+    /// # Panics
+    ///
+    /// Panics when `at` is greater than `len()`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "`at` split index (is {}) should be <= len (is {})", at, self.len);
+        let (first, bit) = Self::locate(at);
+        let len = self.len - at;
+        let mut words = Vec::with_capacity(Self::words_for(len));
+        for word in first..first + Self::words_for(len) {
+            let next = if word + 1 < self.words.len() { self.word(word + 1) } else { W::zero() };
+            words.push(O::arrange(W::funnel_shr(next, self.word(word), bit)));
+        }
+        self.truncate(at);
+        BitVec { words, len, order: PhantomData }
+    }
+
+    /// Keeps the bits turned on in both this vector and `other`, a word at a time
+    ///
+    /// # Panics
+    ///
+    /// Panics when the vectors have different lengths.
+    pub fn and(&mut self, other: &Self) {
+        self.zip_words(other, |word, other| word & other);
+    }
+
+    /// Keeps the bits turned on in this vector or in `other`, a word at a time
+    ///
+    /// # Panics
+    ///
+    /// Panics when the vectors have different lengths.
+    pub fn or(&mut self, other: &Self) {
+        self.zip_words(other, |word, other| word | other);
+    }
+
+    /// Keeps the bits turned on in exactly one of this vector and `other`, a word at a time
+    ///
+    /// # Panics
+    ///
+    /// Panics when the vectors have different lengths.
+    pub fn xor(&mut self, other: &Self) {
+        self.zip_words(other, |word, other| word ^ other);
+    }
+
+    /// Flips all the bits of the vector, a word at a time
+    pub fn not(&mut self) {
+        for word in self.words.iter_mut() {
+            *word = word.flip();
+        }
+        self.clear_unused_bits();
+    }
+
+    /// Returns the number of bits turned on, counting a word at a time
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones_in_range_unchecked(..)).sum()
+    }
+
+    /// Serialises the words of the vector, the first word first, writing the bytes of each
+    /// word in the byte order `E`
+    ///
+    /// **Note:** Each word takes `(bit_size() + 7) / 8` bytes. The unused bits of the last
+    /// word and the high bits of a partial byte are written as zeros.
+    pub fn to_bytes<E: ByteOrder>(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.words.len() * bitorder::word_bytes::<W>());
+        for &word in &self.words {
+            bitorder::write_word::<W, E>(word, &mut bytes);
+        }
+        bytes
+    }
+
+    /// Reads a vector of `len` bits from words serialised by `to_bytes` with the byte order `E`
+    ///
+    /// **Note:** Returns `None` when `bytes` does not hold exactly the words needed for `len`
+    /// bits. The unused bits of the last word and the high bits of a partial byte are ignored.
+    pub fn from_bytes<E: ByteOrder>(bytes: &[u8], len: usize) -> Option<Self> {
+        let word_bytes = bitorder::word_bytes::<W>();
+        if bytes.len() != Self::words_for(len) * word_bytes {
+            return None;
+        }
+        let words = bytes.chunks(word_bytes).map(bitorder::read_word::<W, E>).collect();
+        let mut bits = BitVec { words, len, order: PhantomData };
+        bits.clear_unused_bits();
+        Some(bits)
+    }
+
+    fn zip_words<F: Fn(W, W) -> W>(&mut self, other: &Self, f: F) {
+        assert_eq!(self.len, other.len, "the bit vectors must have the same length");
+        for (word, &other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word = f(*word, other);
+        }
+    }
+
+    fn clear_unused_bits(&mut self) {
+        let (word, bit) = Self::locate(self.len);
+        if bit != 0 {
+            self.set_word(word, self.word(word).clear_range_unchecked(bit..));
+        }
+    }
+
+    /// Returns the word in the index position with its first bit as the least significant.
+    fn word(&self, index: usize) -> W {
+        O::arrange(self.words[index])
+    }
+
+    /// Stores a word given with its first bit as the least significant.
+    fn set_word(&mut self, index: usize, word: W) {
+        self.words[index] = O::arrange(word);
+    }
+
+    fn locate(index: usize) -> (usize, usize) {
+        (index / W::bit_size(), index % W::bit_size())
+    }
+
+    fn words_for(bits: usize) -> usize {
+        (bits + W::bit_size() - 1) / W::bit_size()
+    }
+}
+
+impl<W: Bitwise, O: BitOrder> Default for BitVec<W, O> {
+    fn default() -> Self {
+        BitVec { words: Vec::new(), len: 0, order: PhantomData }
+    }
+}
+
+impl<W: Bitwise, O: BitOrder> Extend<bool> for BitVec<W, O> {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, values: I) {
+        for value in values {
+            self.push(value);
+        }
+    }
+}
+
+impl<W: Bitwise, O: BitOrder> FromIterator<bool> for BitVec<W, O> {
+    fn from_iter<I: IntoIterator<Item = bool>>(values: I) -> Self {
+        let mut bits = BitVec::default();
+        bits.extend(values);
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitVec;
+    use crate::bitorder::{BigEndian, BitOrder, LittleEndian, Msb0};
+    use crate::{BitArray, Bitwise, UInt};
+
+    fn pattern(len: usize) -> Vec<bool> {
+        (0..len).map(|index| index % 3 == 0 || index % 7 == 2).collect()
+    }
+
+    fn bits<W: Bitwise, O: BitOrder>(vec: &BitVec<W, O>) -> Vec<bool> {
+        (0..vec.len()).map(|index| vec.get(index).unwrap()).collect()
+    }
+
+    fn check_invariant<W: Bitwise, O: BitOrder>(vec: &BitVec<W, O>) {
+        assert_eq!(vec.as_words().len(), (vec.len() + W::bit_size() - 1) / W::bit_size());
+        let used = vec.len() % W::bit_size();
+        if let (Some(&last), true) = (vec.as_words().last(), used != 0) {
+            assert_eq!(O::arrange(last).count_ones_in_range_unchecked(used..), 0);
+        }
+    }
+
+    macro_rules! check_against_vec {
+        ($($t:ty),*) => {$(
+            for len in 0..70 {
+                let expected = pattern(len);
+                let vec: $t = expected.iter().copied().collect();
+                assert_eq!(bits(&vec), expected);
+                assert_eq!(vec.count_ones(), expected.iter().filter(|&&bit| bit).count());
+                check_invariant(&vec);
+                for index in 0..=len {
+                    let (mut inserted, mut model) = (vec.clone(), expected.clone());
+                    inserted.insert(index, true);
+                    model.insert(index, true);
+                    assert_eq!(bits(&inserted), model);
+                    check_invariant(&inserted);
+                    let (mut split, mut model) = (vec.clone(), expected.clone());
+                    let tail = split.split_off(index);
+                    let model_tail = model.split_off(index);
+                    assert_eq!((bits(&split), bits(&tail)), (model, model_tail));
+                    check_invariant(&split);
+                    check_invariant(&tail);
+                }
+                for index in 0..len {
+                    let (mut removed, mut model) = (vec.clone(), expected.clone());
+                    assert_eq!(removed.remove(index), model.remove(index));
+                    assert_eq!(bits(&removed), model);
+                    check_invariant(&removed);
+                }
+            }
+        )*};
+    }
+
+    #[test]
+    fn matches_vec_of_bool() {
+        check_against_vec!(BitVec<u8>, BitVec<i16>, BitVec<u64>, BitVec<BitArray<128>>);
+    }
+
+    #[test]
+    fn matches_vec_of_bool_in_msb0_order() {
+        check_against_vec!(BitVec<u8, Msb0>, BitVec<i32, Msb0>, BitVec<BitArray<128>, Msb0>);
+    }
+
+    #[test]
+    fn msb0_order() {
+        let mut vec = BitVec::<u8, _>::with_order(Msb0);
+        vec.extend_from_slice(&[true, true, false, true]);
+        assert_eq!(vec.as_words(), &[0b1101_0000]);
+        vec.remove(0);
+        vec.resize(10, true);
+        assert_eq!(vec.as_words(), &[0b1011_1111, 0b1100_0000]);
+    }
+
+    #[test]
+    fn to_bytes() {
+        let lsb0: BitVec<u16> = (0..10).map(|index| index == 0 || index == 9).collect();
+        assert_eq!(lsb0.to_bytes::<LittleEndian>(), [0b0000_0001, 0b0000_0010]);
+        assert_eq!(lsb0.to_bytes::<BigEndian>(), [0b0000_0010, 0b0000_0001]);
+        let msb0: BitVec<u16, Msb0> = vec![true; 3].into_iter().collect();
+        assert_eq!(msb0.to_bytes::<BigEndian>(), [0b1110_0000, 0]);
+        assert!(BitVec::<u64>::new().to_bytes::<BigEndian>().is_empty());
+    }
+
+    #[test]
+    fn from_bytes() {
+        for len in 0..40 {
+            let vec: BitVec<u16, Msb0> = pattern(len).into_iter().collect();
+            let bytes = vec.to_bytes::<BigEndian>();
+            assert_eq!(BitVec::<u16, Msb0>::from_bytes::<BigEndian>(&bytes, len), Some(vec.clone()));
+            let vec: BitVec<i32> = pattern(len).into_iter().collect();
+            let bytes = vec.to_bytes::<LittleEndian>();
+            assert_eq!(BitVec::<i32>::from_bytes::<LittleEndian>(&bytes, len), Some(vec));
+        }
+        let vec = BitVec::<u8>::from_bytes::<BigEndian>(&[0xFF, 0xFF], 12).unwrap();
+        assert_eq!(vec.as_words(), &[0xFF, 0x0F]);
+        assert_eq!(BitVec::<u8>::from_bytes::<BigEndian>(&[0xFF, 0xFF], 8), None);
+        assert_eq!(BitVec::<u16>::from_bytes::<BigEndian>(&[0xFF], 8), None);
+    }
+
+    #[test]
+    fn bytes_of_words_with_a_partial_byte() {
+        let ones: BitVec<UInt<12>> = vec![true; 12].into_iter().collect();
+        assert_eq!(ones.to_bytes::<BigEndian>(), [0x0F, 0xFF]);
+        assert_eq!(ones.to_bytes::<LittleEndian>(), [0xFF, 0x0F]);
+        for len in 0..40 {
+            let vec: BitVec<UInt<12>> = pattern(len).into_iter().collect();
+            let bytes = vec.to_bytes::<BigEndian>();
+            assert_eq!(bytes.len(), 2 * ((len + 11) / 12));
+            assert_eq!(BitVec::from_bytes::<BigEndian>(&bytes, len), Some(vec));
+            let vec: BitVec<UInt<3>, Msb0> = pattern(len).into_iter().collect();
+            let bytes = vec.to_bytes::<LittleEndian>();
+            assert_eq!(BitVec::from_bytes::<LittleEndian>(&bytes, len), Some(vec));
+        }
+        assert_eq!(BitVec::<UInt<3>>::from_bytes::<BigEndian>(&[], 3), None);
+        assert_eq!(BitVec::<UInt<3>>::from_bytes::<BigEndian>(&[0xFF], 3).unwrap().count_ones(), 3);
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut vec: BitVec<u8> = BitVec::new();
+        assert_eq!(vec.pop(), None);
+        for index in 0..20 {
+            vec.push(index % 2 == 0);
+        }
+        assert_eq!(vec.len(), 20);
+        assert_eq!(vec.as_words(), &[0b0101_0101, 0b0101_0101, 0b0101]);
+        assert_eq!(vec.pop(), Some(false));
+        assert_eq!(vec.pop(), Some(true));
+        assert_eq!(vec.len(), 18);
+        assert_eq!(vec.as_words(), &[0b0101_0101, 0b0101_0101, 0b01]);
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut vec: BitVec<i8> = BitVec::new();
+        vec.resize(10, false);
+        vec.set(7, true);
+        vec.set(9, true);
+        assert_eq!(vec.get(7), Some(true));
+        assert_eq!(vec.get(8), Some(false));
+        assert_eq!(vec.get(10), None);
+        assert_eq!(vec.as_words(), &[i8::MIN, 0b10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn set_out_of_bounds() {
+        let mut vec: BitVec<u32> = BitVec::new();
+        vec.set(0, true);
+    }
+
+    #[test]
+    fn truncate_and_resize() {
+        let mut vec: BitVec<u8> = BitVec::new();
+        vec.resize(13, true);
+        assert_eq!(vec.as_words(), &[0xFF, 0x1F]);
+        vec.truncate(5);
+        assert_eq!(vec.as_words(), &[0x1F]);
+        vec.resize(20, false);
+        assert_eq!(vec.as_words(), &[0x1F, 0, 0]);
+        vec.resize(22, true);
+        assert_eq!(vec.as_words(), &[0x1F, 0, 0x30]);
+        vec.truncate(30);
+        assert_eq!(vec.len(), 22);
+    }
+
+    #[test]
+    fn bulk_operations() {
+        let a: BitVec<u16> = pattern(40).into_iter().collect();
+        let b: BitVec<u16> = (0..40).map(|index| index % 2 == 0).collect();
+        let expected = |f: fn(bool, bool) -> bool| -> Vec<bool> {
+            pattern(40).into_iter().zip(0..40).map(|(x, index)| f(x, index % 2 == 0)).collect()
+        };
+        let mut and = a.clone();
+        and.and(&b);
+        assert_eq!(bits(&and), expected(|x, y| x & y));
+        let mut or = a.clone();
+        or.or(&b);
+        assert_eq!(bits(&or), expected(|x, y| x | y));
+        let mut xor = a.clone();
+        xor.xor(&b);
+        assert_eq!(bits(&xor), expected(|x, y| x ^ y));
+        let mut not = a.clone();
+        not.not();
+        check_invariant(&not);
+        assert_eq!(not.count_ones(), 40 - a.count_ones());
+    }
+
+    #[test]
+    #[should_panic(expected = "the bit vectors must have the same length")]
+    fn bulk_operations_with_different_lengths() {
+        let mut a: BitVec<u8> = BitVec::new();
+        a.push(true);
+        a.and(&BitVec::new());
+    }
+}